@@ -1,16 +1,41 @@
 mod tests;
 
+use core::fmt;
 use core::hint::unreachable_unchecked;
 use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+use core::str::Utf8Error;
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
 
 /// An iterator over a slice.
+#[derive(Clone)]
 pub struct Cursor<'a> {
+    /// The pointer to the first element.
+    first: *const u8,
+    
     /// The pointer to the next element.
     cursor: *const u8,
     
     /// The pointer to the past-the-end element.
     end: *const u8,
     
+    /// The pointer to the past-the-end element of the whole buffer, which `end` can be restored up to.
+    limit: *const u8,
+    
+    /// The value of the index at `index_origin_offset`.
+    index_origin: u64,
+    
+    /// The offset from the first byte at which the index is `index_origin`.
+    index_origin_offset: usize,
+    
+    /// The number of columns between tab stops.
+    tab_width: u8,
+    
+    /// The name of the input, like a file name, for diagnostics.
+    name: Option<&'a str>,
+    
     /// The marker for ownership of `&[u8]`.
     _marker: PhantomData<&'a [u8]>,
 }
@@ -57,6 +82,404 @@ pub enum Error {
     
     /// The fourth byte of a four byte sequence is not a continuation byte.
     Invalid4thOf4,
+    
+    /// There is no byte to decode.
+    Empty,
+    
+    /// The decoded value is a surrogate or greater than `U+10FFFF`.
+    InvalidCodepoint,
+    
+    /// The input ended within a sequence, but the bytes present are valid.
+    Incomplete,
+    
+    /// The sequence encodes a value that fits in a shorter sequence.
+    Overlong,
+}
+
+impl Error {
+    /// All errors, in the order of their discriminants.
+    pub const ALL: [Error; 17] = [
+        Error::EncounteredContinuationByte,
+        Error::Missing2ndOf2,
+        Error::Invalid2ndOf2,
+        Error::Missing2ndOf3,
+        Error::Invalid2ndOf3,
+        Error::Missing3rdOf3,
+        Error::Invalid3rdOf3,
+        Error::Missing2ndOf4,
+        Error::Invalid2ndOf4,
+        Error::Missing3rdOf4,
+        Error::Invalid3rdOf4,
+        Error::Missing4thOf4,
+        Error::Invalid4thOf4,
+        Error::Empty,
+        Error::InvalidCodepoint,
+        Error::Incomplete,
+        Error::Overlong,
+    ];
+    
+    /// Converts the error to its discriminant.
+    #[inline]
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+    
+    /// Converts a discriminant back to the error. Returns `None` if no error has this discriminant.
+    #[inline]
+    pub const fn from_u8(v: u8) -> Option<Error> {
+        if (v as usize) < Self::ALL.len() {
+            Some(Self::ALL[v as usize])
+        } else {
+            None
+        }
+    }
+    
+    /// Checks if the error is caused by the input ending within a sequence.
+    #[inline]
+    pub const fn is_missing(self) -> bool {
+        matches!(
+            self,
+            Error::Missing2ndOf2
+                | Error::Missing2ndOf3
+                | Error::Missing3rdOf3
+                | Error::Missing2ndOf4
+                | Error::Missing3rdOf4
+                | Error::Missing4thOf4
+                | Error::Incomplete
+        )
+    }
+}
+
+/// An [Error] annotated with where it occurred, created by [Cursor::with_position].
+/// 
+/// Displays in the format compilers use, `name:line:column: error`, or `line:column: error` if the input
+/// has no name.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct PositionedError<'a> {
+    /// The error.
+    pub error: Error,
+    
+    /// The index of the cursor when the error occurred.
+    pub index: u64,
+    
+    /// The next byte when the error occurred, or `None` at the end.
+    pub found: Option<u8>,
+    
+    /// The line and column of the cursor when the error occurred, as by [Cursor::line_column].
+    pub line_column: (u32, u32),
+    
+    /// The name of the input, see [Cursor::new_named].
+    pub name: Option<&'a str>,
+}
+
+impl fmt::Display for PositionedError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.name {
+            write!(f, "{name}:")?;
+        }
+        
+        write!(f, "{}:{}: {:?}", self.line_column.0, self.line_column.1, self.error)
+    }
+}
+
+/// A JSON number consumed by [Cursor::consume_json_number].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct JsonNumberSpan<'a> {
+    /// The bytes of the number.
+    pub bytes: &'a [u8],
+    
+    /// Whether the number has a fraction part.
+    pub has_fraction: bool,
+    
+    /// Whether the number has an exponent part.
+    pub has_exponent: bool,
+}
+
+/// Errors [Cursor::take_balanced] can produce.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum BalanceError {
+    /// The cursor is not on the opening byte.
+    MissingOpen,
+    
+    /// The input ended before the opening byte at the index was closed.
+    Unbalanced(u64),
+}
+
+/// Errors [Cursor::consume_template] can produce.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum TemplateError {
+    /// The cursor is not on the opening byte.
+    MissingOpen,
+    
+    /// The input ended before the opening byte at the index was closed.
+    Unterminated(u64),
+}
+
+/// Errors [Cursor::consume_crlf] can produce.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum CrlfError {
+    /// A CR is followed by the byte instead of LF, or by `None` at the end.
+    LoneCr(Option<u8>),
+    
+    /// An LF is not preceded by CR.
+    LoneLf,
+    
+    /// The cursor is on the byte instead of CR, or `None` at the end.
+    Missing(Option<u8>),
+}
+
+/// The error [Cursor::expect_one_of] produces. Displays as "expected one of ..., found ...".
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct ExpectError<'s> {
+    /// The byte found instead, or `None` if the input ended.
+    pub found: Option<u8>,
+    
+    /// The allowed bytes.
+    pub expected: &'s [u8],
+}
+
+impl fmt::Display for ExpectError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected one of ")?;
+        
+        for (i, x) in self.expected.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}`", x.escape_ascii())?;
+        }
+        
+        match self.found {
+            Some(x) => write!(f, ", found `{}`", x.escape_ascii()),
+            None => f.write_str(", found the end of the input"),
+        }
+    }
+}
+
+/// Errors [Cursor::consume_escape] can produce.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum EscapeError {
+    /// The byte does not start a known escape.
+    Unknown(u8),
+    
+    /// The input ended within the escape.
+    End,
+    
+    /// A `\x` escape is not followed by two hex digits, or a `\u` escape is not followed by one to six
+    /// hex digits in braces.
+    InvalidHex,
+    
+    /// The value is above `0x7F` in a `\x` escape, or is a surrogate or above `U+10FFFF` in a `\u` escape.
+    OutOfRange(u32),
+}
+
+/// Errors [Cursor::consume_delimited] can produce.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum DelimError {
+    /// The remaining bytes do not start with the opening delimiter.
+    MissingOpen,
+    
+    /// The input ended before the opening delimiter at the index was closed.
+    Unterminated(u64),
+}
+
+/// The error [Cursor::expect_slice] produces.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct MismatchError {
+    /// The offset of the mismatching byte in the literal.
+    pub offset: usize,
+    
+    /// The byte found instead, or `None` if the input ended.
+    pub found: Option<u8>,
+}
+
+/// Errors [Cursor::expect_char] can produce. Each carries the index of the cursor, which did not advance.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum ExpectCharError {
+    /// Found a different char.
+    Mismatch(char, u64),
+    
+    /// The input ended.
+    End(u64),
+    
+    /// The next bytes are not valid UTF-8.
+    Invalid(Error, u64),
+}
+
+/// The error [Cursor::skip_block_comment] produces.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum CommentError {
+    /// The input ended before the comment opened at the index was closed.
+    Unterminated(u64),
+}
+
+/// A convention for line terminators, used by [Cursor::next_line_ending].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum LineEnding {
+    /// Lines end with LF.
+    Lf,
+    
+    /// Lines end with CRLF.
+    CrLf,
+    
+    /// Lines end with CR.
+    Cr,
+    
+    /// Lines end with any of LF, CRLF and CR, like [Cursor::next_lfn] treats them.
+    Any,
+}
+
+/// A byte order mark, detected by [Cursor::detect_bom].
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Bom {
+    /// The UTF-8 mark `EF BB BF`.
+    Utf8,
+    
+    /// The UTF-16 little endian mark `FF FE`.
+    Utf16Le,
+    
+    /// The UTF-16 big endian mark `FE FF`.
+    Utf16Be,
+    
+    /// The UTF-32 little endian mark `FF FE 00 00`.
+    Utf32Le,
+    
+    /// The UTF-32 big endian mark `00 00 FE FF`.
+    Utf32Be,
+}
+
+impl Bom {
+    /// Gets the bytes of the mark.
+    #[inline]
+    pub const fn bytes(self) -> &'static [u8] {
+        match self {
+            Bom::Utf8 => b"\xEF\xBB\xBF",
+            Bom::Utf16Le => b"\xFF\xFE",
+            Bom::Utf16Be => b"\xFE\xFF",
+            Bom::Utf32Le => b"\xFF\xFE\x00\x00",
+            Bom::Utf32Be => b"\x00\x00\xFE\xFF",
+        }
+    }
+}
+
+/// The end of a cursor before [Cursor::set_end], restored by [Cursor::restore_end].
+#[derive(Debug)]
+pub struct PrevEnd {
+    /// The pointer to the past-the-end element.
+    end: *const u8,
+}
+
+/// A position of a cursor, created by [Cursor::checkpoint] and restored by [Cursor::restore].
+/// 
+/// Checkpoints are ordered by position, so `after > before` asserts that a cursor made progress.
+#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Checkpoint {
+    /// The pointer to the next element.
+    cursor: *const u8,
+    
+    /// The pointer to the first element of the cursor that created the checkpoint.
+    first: *const u8,
+}
+
+impl Checkpoint {
+    /// Gets the signed number of bytes from this checkpoint to `other`.
+    #[inline]
+    pub fn distance(&self, other: &Checkpoint) -> i64 {
+        (other.cursor as isize - self.cursor as isize) as i64
+    }
+}
+
+/// A set of keywords with ids, matched by [Cursor::consume_prefix_tree] in time linear in the length of
+/// the match.
+#[cfg(feature = "alloc")]
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct PrefixTree {
+    /// The nodes, with the root first.
+    nodes: Vec<PrefixNode>,
+}
+
+/// A node of a [PrefixTree].
+#[cfg(feature = "alloc")]
+#[derive(Eq, PartialEq, Clone, Debug)]
+struct PrefixNode {
+    /// The bytes leading to the children and their indices, sorted by byte.
+    edges: Vec<(u8, usize)>,
+    
+    /// The id of the keyword that ends at this node.
+    id: Option<u32>,
+}
+
+#[cfg(feature = "alloc")]
+impl PrefixTree {
+    /// Builds a tree from keywords and their ids. If a keyword occurs more than once, the last id wins.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if a keyword is empty, like [crate::match_keyword!] fails to compile.
+    pub fn build(keywords: &[(&[u8], u32)]) -> PrefixTree {
+        let mut nodes = vec![PrefixNode { edges: Vec::new(), id: None }];
+        
+        for &(keyword, id) in keywords {
+            assert!(!keyword.is_empty(), "keywords must not be empty");
+            
+            let mut node = 0;
+            
+            for &x in keyword {
+                node = match nodes[node].edges.binary_search_by_key(&x, |&(y, _)| y) {
+                    Ok(i) => nodes[node].edges[i].1,
+                    Err(i) => {
+                        let child = nodes.len();
+                        nodes[node].edges.insert(i, (x, child));
+                        nodes.push(PrefixNode { edges: Vec::new(), id: None });
+                        child
+                    }
+                };
+            }
+            
+            nodes[node].id = Some(id);
+        }
+        
+        PrefixTree { nodes }
+    }
+    
+    /// Finds the longest keyword the bytes start with. Returns its id and length.
+    #[inline]
+    fn longest_match(&self, bytes: &[u8]) -> Option<(u32, usize)> {
+        let mut node = &self.nodes[0];
+        let mut found = None;
+        
+        for (i, &x) in bytes.iter().enumerate() {
+            let Ok(edge) = node.edges.binary_search_by_key(&x, |&(y, _)| y) else {
+                break;
+            };
+            
+            node = &self.nodes[node.edges[edge].1];
+            
+            if let Some(id) = node.id {
+                found = Some((id, i + 1));
+            }
+        }
+        
+        found
+    }
+}
+
+/// Records the bytes consumed through it. Created by [Cursor::begin_recording].
+pub struct Recorder<'a, 'b> {
+    /// The recording cursor.
+    cursor: &'b mut Cursor<'a>,
+    
+    /// The pointer to the first recorded byte.
+    start: *const u8,
+}
+
+/// An iterator that consumes chunks of a fixed size. Created by [Cursor::chunks].
+pub struct Chunks<'a, 'b> {
+    /// The consumed cursor.
+    cursor: &'b mut Cursor<'a>,
+    
+    /// The number of bytes in each chunk but the last.
+    size: usize,
 }
 
 impl<'a> Cursor<'a> {
@@ -78,16 +501,124 @@ impl<'a> Cursor<'a> {
         self.cursor
     }
     
+    /// Gets the index of the cursor. This is the number of bytes consumed, unless the index was changed
+    /// by [Cursor::reset_index].
+    #[inline]
+    pub const fn index(&self) -> u64 {
+        self.index_at(self.byte_offset())
+    }
+    
+    /// Gets the offset of the cursor from the first byte. Unlike [Cursor::index], this is always the
+    /// number of bytes consumed.
+    #[inline]
+    pub const fn byte_offset(&self) -> usize {
+        unsafe { self.cursor.offset_from_unsigned(self.first) }
+    }
+    
+    /// Sets the index of the cursor to `value` without moving the cursor, for example to report offsets
+    /// relative to the start of a segment. The index keeps counting from there as the cursor moves.
+    /// 
+    /// Afterward, [Cursor::index] no longer equals the number of bytes consumed; [Cursor::byte_offset]
+    /// still does.
+    #[inline]
+    pub fn reset_index(&mut self, value: u64) {
+        self.index_origin = value;
+        self.index_origin_offset = self.byte_offset();
+    }
+    
+    /// Gets the index at `offset` from the first byte.
+    /// 
+    /// Panics on overflow and underflow in debug builds and wraps around in release builds.
+    #[inline]
+    const fn index_at(&self, offset: usize) -> u64 {
+        if offset >= self.index_origin_offset {
+            self.inc_index((offset - self.index_origin_offset) as u64)
+        } else {
+            self.dec_index((self.index_origin_offset - offset) as u64)
+        }
+    }
+    
+    /// Adds `n` to the index origin. Panics on overflow in debug builds.
+    #[inline]
+    const fn inc_index(&self, n: u64) -> u64 {
+        if cfg!(debug_assertions) {
+            match self.index_origin.checked_add(n) {
+                Some(index) => index,
+                None => panic!("index overflow"),
+            }
+        } else {
+            self.index_origin.wrapping_add(n)
+        }
+    }
+    
+    /// Subtracts `n` from the index origin. Panics on underflow in debug builds.
+    #[inline]
+    const fn dec_index(&self, n: u64) -> u64 {
+        if cfg!(debug_assertions) {
+            match self.index_origin.checked_sub(n) {
+                Some(index) => index,
+                None => panic!("index underflow"),
+            }
+        } else {
+            self.index_origin.wrapping_sub(n)
+        }
+    }
+    
+    /// Gets the offset from the first byte at which the index is `index`.
+    #[inline]
+    fn offset_of(&self, index: u64) -> Option<usize> {
+        if index >= self.index_origin {
+            self.index_origin_offset.checked_add(usize::try_from(index - self.index_origin).ok()?)
+        } else {
+            self.index_origin_offset.checked_sub(usize::try_from(self.index_origin - index).ok()?)
+        }
+    }
+    
     #[inline]
-    pub const fn new(slice: &[u8]) -> Self {
+    pub const fn new(slice: &'a [u8]) -> Self {
         Self {
+            first: slice.as_ptr(),
             cursor: slice.as_ptr(),
             end: unsafe { slice.as_ptr().add(slice.len()) },
+            limit: unsafe { slice.as_ptr().add(slice.len()) },
+            index_origin: 0,
+            index_origin_offset: 0,
+            tab_width: 1,
+            name: None,
             _marker: PhantomData,
         }
     }
     
+    /// Creates a cursor for an input with a name, like a file name, which [PositionedError] includes.
+    /// The name does not affect parsing.
+    #[inline]
+    pub const fn new_named(slice: &'a [u8], name: &'a str) -> Self {
+        let mut cursor = Self::new(slice);
+        cursor.name = Some(name);
+        cursor
+    }
+    
+    /// Gets the name of the input given to [Cursor::new_named].
+    #[inline]
+    pub const fn input_name(&self) -> Option<&'a str> {
+        self.name
+    }
+    
+    /// Creates a cursor whose index starts at `base` instead of 0, like [Cursor::reset_index] right after
+    /// [Cursor::new]. Meant for buffers that are part of a larger input, like one of several
+    /// concatenated files, so indices stay unique across them. [Cursor::byte_offset] is not affected
+    /// and still starts at 0.
+    #[inline]
+    pub const fn new_with_base(slice: &'a [u8], base: u64) -> Self {
+        let mut cursor = Self::new(slice);
+        cursor.index_origin = base;
+        cursor
+    }
+    
     /// Gets the next byte. Normalizes line terminators by mapping CR, CRLF and LF sequences to LF.
+    /// 
+    /// A CR that is followed by another byte than LF, or ends the input, is mapped to LF on its own;
+    /// the following byte is left for the next call.
     #[inline]
     pub fn next_lfn(&mut self) -> Option<u8> {
         match self.peek() {
@@ -110,6 +641,7 @@ impl<'a> Cursor<'a> {
     
     /// Gets the next byte. Does not normalize line terminators.
     #[inline]
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<u8> {
         if self.has_next() {
             let byte = unsafe { self.peek_unchecked() };
@@ -142,18 +674,87 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    /// Peeks into the next byte if it is ASCII. Does not advance the iterator.
+    #[inline]
+    pub fn peek_ascii(&self) -> Option<u8> {
+        self.peek().filter(u8::is_ascii)
+    }
+    
+    /// Checks if the next byte is `byte`. Does not advance the iterator.
+    #[inline]
+    pub fn peek_eq(&self, byte: u8) -> bool {
+        self.peek() == Some(byte)
+    }
+    
+    /// Checks if the next byte is in `set`. Does not advance the iterator.
+    #[inline]
+    pub fn peek_in(&self, set: &[u8]) -> bool {
+        self.peek().is_some_and(|x| set.contains(&x))
+    }
+    
     /// Peeks into the nth byte, first byte is n=0. Does not advance.
     #[inline]
     pub fn peek_n(&self, n: usize) -> Option<u8> {
-        let desired_byte_ptr = unsafe { self.cursor.add(n) };
-
-        if desired_byte_ptr < self.end {
-            Some(unsafe { *desired_byte_ptr })
+        self.remaining().get(n).copied()
+    }
+    
+    /// Gets the whole buffer, including the consumed bytes.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u8] {
+        unsafe { slice::from_raw_parts(self.first, self.end.offset_from_unsigned(self.first)) }
+    }
+    
+    /// Gets the whole buffer as a string slice, validating it as UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Result<&'a str, Utf8Error> {
+        core::str::from_utf8(self.as_slice())
+    }
+    
+    /// Gets the whole buffer as a string slice.
+    /// 
+    /// # Safety
+    /// 
+    /// The caller must ensure that the buffer is valid UTF-8.
+    #[inline]
+    pub unsafe fn as_str_unchecked(&self) -> &'a str {
+        core::str::from_utf8_unchecked(self.as_slice())
+    }
+    
+    /// Gets the bytes that have been consumed.
+    #[inline]
+    pub fn consumed(&self) -> &'a [u8] {
+        unsafe { self.slice_from(self.first) }
+    }
+    
+    /// Gets the bytes that have not been consumed yet.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        unsafe { slice::from_raw_parts(self.cursor, self.end.offset_from_unsigned(self.cursor)) }
+    }
+    
+    /// Peeks into the previous byte. Does not rewind the iterator.
+    #[inline]
+    pub fn peek_back(&self) -> Option<u8> {
+        if self.can_rewind() {
+            Some(unsafe { *self.cursor.sub(1) })
         } else {
             None
         }
     }
     
+    /// Gets the number of bytes that have not been consumed yet.
+    #[inline]
+    pub fn remaining_len(&self) -> usize {
+        unsafe { self.end.offset_from_unsigned(self.cursor) }
+    }
+    
+    /// Gets an upper bound of the number of chars that have not been consumed yet, which is exact
+    /// for ASCII input. Meant for sizing allocations, like `Vec::with_capacity` for a `Vec<char>`.
+    #[inline]
+    pub fn estimate_chars_remaining(&self) -> usize {
+        self.remaining_len()
+    }
+    
     /// Checks if the cursor has a next byte.
     #[inline]
     pub fn has_next(&self) -> bool {
@@ -170,9 +771,8 @@ impl<'a> Cursor<'a> {
         *self.cursor
     }
 
-    /*
+    /// Rewinds one byte. Treats a CRLF sequence as a single byte.
     #[inline]
-    #[deprecated]
     pub fn rewind_lfn(&mut self) {
         if self.can_rewind() {
             self.cursor = unsafe { self.cursor.sub(1) };
@@ -187,20 +787,17 @@ impl<'a> Cursor<'a> {
     
     /// Checks if the cursor can be rewinded.
     #[inline]
-    #[deprecated]
-    pub fn can_rewind(&mut self) -> bool {
+    pub fn can_rewind(&self) -> bool {
         self.cursor > self.first
     }
     
     /// Rewinds one byte. Saturates at the lower boundary.
     #[inline]
-    #[deprecated]
     pub fn rewind(&mut self) {
         if self.can_rewind() {
             unsafe { self.rewind_unchecked(); }
         }
     }
-    */
     
     /// Rewinds one byte.
     /// 
@@ -209,6 +806,7 @@ impl<'a> Cursor<'a> {
     /// The caller must ensure that the cursor can rewind.
     #[inline]
     pub unsafe fn rewind_unchecked(&mut self) {
+        debug_assert!(self.can_rewind(), "rewound past the first byte");
         self.cursor = self.cursor.sub(1);
     }
     
@@ -229,13 +827,54 @@ impl<'a> Cursor<'a> {
     pub unsafe fn advance_unchecked(&mut self) {
         self.cursor = self.cursor.add(1)
     }
+    
+    /// Advances the cursor `n` bytes.
+    /// 
+    /// # Safety
+    /// 
+    /// The caller must ensure that at least `n` bytes remain.
+    #[inline]
+    pub unsafe fn advance_n_unchecked(&mut self, n: usize) {
+        self.cursor = self.cursor.add(n)
+    }
 
+    /// Advances the cursor by one char encoded as UTF-8. A CRLF sequence counts as one char.
+    /// 
+    /// Unlike [Cursor::advance_char], which only validates the structure of the sequence, this also
+    /// rejects overlong sequences with [Error::Overlong] (so `E0 80 AF` never passes as `/`), and
+    /// surrogates and values greater than `U+10FFFF` with [Error::InvalidCodepoint]. It also does not
+    /// advance on error, whereas [Cursor::advance_char] may have consumed part of the sequence.
+    /// 
+    /// Both methods reject the bytes `C0`, `C1` and `F5` to `FF`, which cannot start a valid sequence,
+    /// with [Error::EncounteredContinuationByte].
+    #[inline]
+    pub fn advance_char_strict(&mut self) -> Result<(), Error> {
+        if !self.has_next() {
+            return Ok(());
+        }
+        
+        let (c, width) = decode_char(self.remaining())?;
+        unsafe { self.advance_n_unchecked(width) }
+        
+        if c == '\r' && self.peek() == Some(b'\n') {
+            unsafe { self.advance_unchecked() }
+        }
+        Ok(())
+    }
+    
+    /// Advances the cursor by the width of the char indicated by the next byte.
+    /// 
+    /// # Safety
+    /// 
+    /// The caller must ensure that the cursor has a next byte and that the whole char is in bounds.
     #[inline]
     pub unsafe fn advance_char_unchecked(&mut self) {
         self.cursor = self.cursor.add(UTF8_CHAR_WIDTH[self.peek_unchecked() as usize] as usize);
     }
     
-    /// Advances the cursor by one char encoded as UTF-8.
+    /// Advances the cursor by one char encoded as UTF-8. A CRLF sequence counts as one char.
+    /// 
+    /// Only the structure of the sequence is validated; see [Cursor::advance_char_strict].
     #[inline]
     pub fn advance_char(&mut self) -> Result<(), Error> {
         let first_byte = match self.next() {
@@ -280,11 +919,1930 @@ impl<'a> Cursor<'a> {
         }
     }
 
-    // #[inline]
-    // pub const fn offset(&self) -> usize {
-    //     unsafe { self.cursor.sub_ptr(self.first) }
-    // }
-}
+    /// Consumes a number as defined by the JSON grammar: an optional `-`, an integer part,
+    /// an optional fraction part and an optional exponent part.
+    /// 
+    /// If `strict` is set, integer parts with leading zeros (like `01`) are rejected.
+    /// Returns `None` without advancing if there is no valid number at the cursor.
+    #[inline]
+    pub fn consume_json_number(&mut self, strict: bool) -> Option<JsonNumberSpan<'a>> {
+        let start = self.cursor;
+        
+        macro_rules! reject {
+            () => {{
+                self.cursor = start;
+                return None;
+            }};
+        }
+        
+        if self.peek() == Some(b'-') {
+            unsafe { self.advance_unchecked() }
+        }
+        
+        match self.peek() {
+            Some(b'0') => {
+                unsafe { self.advance_unchecked() }
+                
+                if matches!(self.peek(), Some(b'0'..=b'9')) {
+                    if strict {
+                        reject!();
+                    }
+                    self.skip_ascii_digits();
+                }
+            }
+            Some(b'1'..=b'9') => {
+                self.skip_ascii_digits();
+            }
+            _ => reject!(),
+        }
+        
+        let has_fraction = self.peek() == Some(b'.');
+        
+        if has_fraction {
+            unsafe { self.advance_unchecked() }
+            
+            if self.skip_ascii_digits() == 0 {
+                reject!();
+            }
+        }
+        
+        let has_exponent = matches!(self.peek(), Some(b'e' | b'E'));
+        
+        if has_exponent {
+            unsafe { self.advance_unchecked() }
+            
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                unsafe { self.advance_unchecked() }
+            }
+            
+            if self.skip_ascii_digits() == 0 {
+                reject!();
+            }
+        }
+        
+        Some(JsonNumberSpan {
+            bytes: unsafe { self.slice_from(start) },
+            has_fraction,
+            has_exponent,
+        })
+    }
+    
+    /// Skips ASCII digits and returns the number of digits skipped.
+    #[inline]
+    fn skip_ascii_digits(&mut self) -> usize {
+        let start = self.cursor;
+        
+        while let Some(b'0'..=b'9') = self.peek() {
+            unsafe { self.advance_unchecked() }
+        }
+        
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Gets the bytes from `start` up to the cursor.
+    /// 
+    /// # Safety
+    /// 
+    /// The caller must ensure that `start` points into the buffer and is not past the cursor.
+    #[inline]
+    unsafe fn slice_from(&self, start: *const u8) -> &'a [u8] {
+        slice::from_raw_parts(start, self.cursor.offset_from_unsigned(start))
+    }
+
+    /// Lazily consumes the rest of the bytes, mapping each byte with `f`.
+    #[inline]
+    pub fn bytes_map<'b, B, F: FnMut(u8) -> B>(&'b mut self, mut f: F) -> impl Iterator<Item = B> + use<'a, 'b, B, F> {
+        core::iter::from_fn(move || self.next().map(&mut f))
+    }
+
+    /// Rewinds the cursor to the start of the current line, that is just after the previous `\n`
+    /// (or `\r`), or to the first byte. Does nothing if the cursor is already at a line start.
+    #[inline]
+    pub fn to_line_start(&mut self) {
+        while self.can_rewind() {
+            match unsafe { *self.cursor.sub(1) } {
+                b'\n' | b'\r' => break,
+                _ => unsafe { self.rewind_unchecked() }
+            }
+        }
+    }
+    
+    /// Advances the cursor to the end of the current line, that is just before the next `\n` or
+    /// `\r`, or to the end. Does nothing if the cursor is already at a line end.
+    #[inline]
+    pub fn to_line_end(&mut self) {
+        while let Some(x) = self.peek() {
+            match x {
+                b'\n' | b'\r' => break,
+                _ => unsafe { self.advance_unchecked() }
+            }
+        }
+    }
+
+    /// Advances while `class[byte]` is `true`. Returns the number of bytes consumed.
+    #[inline]
+    pub fn consume_class(&mut self, class: &[bool; 256]) -> usize {
+        let start = self.cursor;
+        
+        while let Some(x) = self.peek() {
+            if !class[x as usize] {
+                break;
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Advances until `class[byte]` is `true` or the end is reached. Returns the number of bytes consumed.
+    #[inline]
+    pub fn consume_until_class(&mut self, class: &[bool; 256]) -> usize {
+        let start = self.cursor;
+
+        while let Some(x) = self.peek() {
+            if class[x as usize] {
+                break;
+            }
+            unsafe { self.advance_unchecked() }
+        }
+
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Gets the number of bytes consumed since `start`, which is an index obtained by [Cursor::index].
+    #[inline]
+    pub fn consumed_since(&self, start: u64) -> u64 {
+        let index = self.index();
+        debug_assert!(start <= index, "start is past the cursor");
+        index - start
+    }
+    
+    /// Gets the next char decoded as UTF-8. Does not normalize line terminators.
+    /// Does not advance on error.
+    #[inline]
+    pub fn next_char(&mut self) -> Result<Option<char>, Error> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        
+        let (c, width) = decode_char(self.remaining())?;
+        unsafe { self.advance_n_unchecked(width) }
+        Ok(Some(c))
+    }
+    
+    /// Begins recording the bytes consumed through the returned [Recorder].
+    #[inline]
+    pub fn begin_recording<'b>(&'b mut self) -> Recorder<'a, 'b> {
+        Recorder {
+            start: self.cursor,
+            cursor: self,
+        }
+    }
+    
+    /// Runs `f` and returns its result together with the text it consumed.
+    /// 
+    /// Returns `None` if `f` returns `None`, leaving the cursor wherever `f` left it.
+    /// Also returns `None` if the consumed bytes are not valid UTF-8, but then the cursor is rewound
+    /// to where `f` started, so the bytes can be consumed again with a stricter parser.
+    #[inline]
+    pub fn scan<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<(T, &'a str)> {
+        let mut recorder = self.begin_recording();
+        let start = recorder.start;
+        let value = f(&mut recorder)?;
+        
+        match core::str::from_utf8(recorder.stop()) {
+            Ok(text) => Some((value, text)),
+            Err(_) => {
+                self.cursor = start;
+                None
+            }
+        }
+    }
+    
+    /// Peeks into the next char decoded as UTF-8. Does not advance the iterator.
+    #[inline]
+    pub fn peek_char(&self) -> Result<Option<char>, Error> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        
+        decode_char(self.remaining()).map(|(c, _)| Some(c))
+    }
+    
+    /// Peeks into the next char decoded as UTF-8, like [Cursor::peek_char], but returns
+    /// [Error::Incomplete] instead of a `Missing...` error if the input ends within the char.
+    /// 
+    /// This lets a streaming caller tell input that needs more bytes apart from malformed input.
+    #[inline]
+    pub fn peek_char_partial(&self) -> Result<Option<char>, Error> {
+        self.peek_char().map_err(|e| if e.is_missing() { Error::Incomplete } else { e })
+    }
+    
+    /// Counts the leading bytes that satisfy `pred`. Does not advance the iterator.
+    #[inline]
+    pub fn count_while<F: FnMut(u8) -> bool>(&self, mut pred: F) -> usize {
+        self.remaining().iter().take_while(|&&x| pred(x)).count()
+    }
+    
+    /// Peeks into the next `n` chars decoded as UTF-8 and returns them as a string slice.
+    /// Does not advance the iterator.
+    /// 
+    /// Returns [Error::Incomplete] if fewer than `n` chars remain.
+    #[inline]
+    pub fn peek_chars(&self, n: usize) -> Result<&'a str, Error> {
+        let remaining = self.remaining();
+        let mut len = 0;
+        
+        for _ in 0..n {
+            match decode_char(&remaining[len..]) {
+                Ok((_, width)) => len += width,
+                Err(e) if e == Error::Empty || e.is_missing() => return Err(Error::Incomplete),
+                Err(e) => return Err(e),
+            }
+        }
+        
+        // SAFETY: Every decoded char is valid UTF-8.
+        Ok(unsafe { core::str::from_utf8_unchecked(&remaining[..len]) })
+    }
+    
+    /// Checks if the cursor is between a word byte and a non-word byte, like the `\b` anchor.
+    /// 
+    /// Word bytes are ASCII letters, ASCII digits and `_`. The start and the end of the input count as
+    /// non-word bytes.
+    #[inline]
+    pub fn at_word_boundary(&self) -> bool {
+        #[inline]
+        fn is_word(x: Option<u8>) -> bool {
+            matches!(x, Some(b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_'))
+        }
+        
+        is_word(self.peek_back()) != is_word(self.peek())
+    }
+    
+    /// Advances while `pred` holds. An `escape` byte is consumed together with the byte after it,
+    /// which is not checked against `pred`, so an escaped delimiter does not stop the scan.
+    /// Returns the number of bytes consumed, including escapes and escaped bytes.
+    #[inline]
+    pub fn consume_while_escaped<F: FnMut(u8) -> bool>(&mut self, escape: u8, mut pred: F) -> usize {
+        let start = self.cursor;
+        
+        while let Some(x) = self.peek() {
+            if x == escape {
+                unsafe { self.advance_unchecked() }
+                self.advance();
+            } else if pred(x) {
+                unsafe { self.advance_unchecked() }
+            } else {
+                break;
+            }
+        }
+        
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Sets the number of columns between tab stops used by [Cursor::line_column].
+    /// A width of 0 or 1 counts a tab as a single column, which is the default.
+    #[inline]
+    pub fn set_tab_width(&mut self, width: u8) {
+        self.tab_width = width;
+    }
+    
+    /// Gets the line and column of the cursor, both starting at 1.
+    /// 
+    /// CR, CRLF and LF sequences each end a line. Columns count chars, not bytes, and a tab advances to
+    /// the next tab stop (see [Cursor::set_tab_width]).
+    #[inline]
+    pub fn line_column(&self) -> (u32, u32) {
+        let (mut line, mut column) = (1, 0);
+        self.scan_line_column(&mut line, &mut column, 0..self.byte_offset());
+        (line, column + 1)
+    }
+    
+    /// Updates `line` and the 0-based `column` over the bytes of the whole buffer in `range`, like
+    /// [Cursor::line_column]. An LF after a CR is skipped, so a CRLF sequence split by consecutive ranges
+    /// ends only one line.
+    #[inline]
+    fn scan_line_column(&self, line: &mut u32, column: &mut u32, range: core::ops::Range<usize>) {
+        let bytes = self.as_slice();
+        let tab_width = self.tab_width.max(1) as u32;
+        
+        for i in range {
+            match bytes[i] {
+                b'\n' if i > 0 && bytes[i - 1] == b'\r' => {}
+                b'\n' | b'\r' => {
+                    *line += 1;
+                    *column = 0;
+                }
+                b'\t' => *column = (*column / tab_width + 1) * tab_width,
+                x if x & 0b1100_0000 == 0b1000_0000 => {}
+                _ => *column += 1,
+            }
+        }
+    }
+    
+    /// Renders the state of the cursor for debugging: the `first`, `cursor` and `end` pointers with their
+    /// byte offsets and the index, the escaped buffer with a `^` under the cursor, and a hex view of up
+    /// to 8 bytes on both sides of the cursor.
+    /// 
+    /// The buffer shows at most 32 bytes on both sides of the cursor. It is enclosed in `|` marks where
+    /// it starts and ends, and in `...` where it is cut off.
+    #[cfg(feature = "alloc")]
+    pub fn dump(&self) -> String {
+        use core::fmt::Write;
+        
+        const WINDOW: usize = 32;
+        
+        let consumed = self.consumed();
+        let remaining = self.remaining();
+        let before = &consumed[consumed.len().saturating_sub(WINDOW)..];
+        let after = &remaining[..remaining.len().min(WINDOW)];
+        let open = if before.len() < consumed.len() { "..." } else { "|" };
+        let close = if after.len() < remaining.len() { "..." } else { "|" };
+        let mut out = String::new();
+        
+        let _ = writeln!(
+            out,
+            "first: {:p} (0), cursor: {:p} ({}), end: {:p} ({}), index: {}",
+            self.first,
+            self.cursor,
+            self.byte_offset(),
+            self.end,
+            self.as_slice().len(),
+            self.index(),
+        );
+        let _ = writeln!(out, "{open}{}{}{close}", before.escape_ascii(), after.escape_ascii());
+        let _ = writeln!(out, "{:>1$}", "^", open.len() + before.escape_ascii().count() + 1);
+        
+        for x in &consumed[consumed.len().saturating_sub(8)..] {
+            let _ = write!(out, "{:02x} ", x);
+        }
+        out.push('|');
+        for x in &remaining[..remaining.len().min(8)] {
+            let _ = write!(out, " {:02x}", x);
+        }
+        out.push('\n');
+        
+        out
+    }
+    
+    /// Consumes a maximal run of identical bytes. Returns the byte and the length of the run,
+    /// or `None` at the end.
+    #[inline]
+    pub fn consume_run(&mut self) -> Option<(u8, usize)> {
+        let byte = self.peek()?;
+        let len = self.count_while(|x| x == byte);
+        unsafe { self.advance_n_unchecked(len) }
+        Some((byte, len))
+    }
+    
+    /// Consumes a line and its terminator (CR, CRLF or LF). Returns the bytes of the line without
+    /// the terminator, or `None` at the end. The bytes are not validated as UTF-8.
+    #[inline]
+    pub fn consume_line_bytes(&mut self) -> Option<&'a [u8]> {
+        if !self.has_next() {
+            return None;
+        }
+        
+        let start = self.cursor;
+        self.to_line_end();
+        let line = unsafe { self.slice_from(start) };
+        let _ = self.next_lfn();
+        Some(line)
+    }
+    
+    /// Consumes the maximal run of digits in `radix` and returns their value. Letters of either case
+    /// are digits from 10 on. Underscores are not skipped, so they end the run. To accept digit groups
+    /// like `1_000`, use [Cursor::consume_u64_separated] with `b'_'` as the separator.
+    /// 
+    /// Returns `None` without advancing if there are no digits or the value overflows `u64`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `radix` is not in the range from 2 to 36.
+    #[inline]
+    pub fn consume_uint_radix(&mut self, radix: u32) -> Option<u64> {
+        assert!((2..=36).contains(&radix), "radix must be in the range from 2 to 36");
+        
+        let start = self.cursor;
+        let mut value: Option<u64> = Some(0);
+        
+        while let Some(digit) = self.peek().and_then(|x| (x as char).to_digit(radix)) {
+            value = value
+                .and_then(|v| v.checked_mul(radix as u64))
+                .and_then(|v| v.checked_add(digit as u64));
+            unsafe { self.advance_unchecked() }
+        }
+        
+        if self.cursor == start || value.is_none() {
+            self.cursor = start;
+            return None;
+        }
+        
+        value
+    }
+    
+    /// Peeks into the bytes of the next char encoded as UTF-8. Does not advance the iterator.
+    /// 
+    /// Returns [Error::Incomplete] if the input ends within the char.
+    #[inline]
+    pub fn peek_char_bytes(&self) -> Result<Option<&'a [u8]>, Error> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        
+        match decode_char(self.remaining()) {
+            Ok((_, width)) => Ok(Some(&self.remaining()[..width])),
+            Err(e) if e.is_missing() => Err(Error::Incomplete),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// Gets the offsets of the line starts in the whole buffer. CR, CRLF and LF sequences each end a line.
+    /// 
+    /// The first entry is always 0. A terminator at the end of the buffer starts an empty last line,
+    /// so its entry is the length of the buffer. This is consistent with [Cursor::line_column].
+    #[cfg(feature = "alloc")]
+    pub fn line_starts(&self) -> Vec<u64> {
+        let bytes = self.as_slice();
+        let mut starts = vec![0];
+        let mut i = 0;
+        
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+                    i += 1;
+                    starts.push(i as u64 + 1);
+                }
+                b'\r' | b'\n' => starts.push(i as u64 + 1),
+                _ => {}
+            }
+            i += 1;
+        }
+        
+        starts
+    }
+    
+    /// Consumes a region from the `open` byte at the cursor to its matching `close` byte, accounting for
+    /// nesting. Returns the bytes between them. Does not advance on error.
+    /// 
+    /// If `open` and `close` are the same byte, there is no nesting and the next occurrence closes.
+    #[inline]
+    pub fn take_balanced(&mut self, open: u8, close: u8) -> Result<&'a [u8], BalanceError> {
+        if self.peek() != Some(open) {
+            return Err(BalanceError::MissingOpen);
+        }
+        
+        let start = self.cursor;
+        let mut depth = 1usize;
+        unsafe { self.advance_unchecked() }
+        
+        while let Some(x) = self.next() {
+            if x == close {
+                depth -= 1;
+                
+                if depth == 0 {
+                    let region = unsafe { self.slice_from(start) };
+                    return Ok(&region[1..region.len() - 1]);
+                }
+            } else if x == open {
+                depth += 1;
+            }
+        }
+        
+        self.cursor = start;
+        Err(BalanceError::Unbalanced(self.index()))
+    }
+    
+    /// Advances the cursor by the width of the char indicated by the next byte, but at least one byte
+    /// and at most to the end. Continuation bytes are not validated. Returns the number of bytes advanced,
+    /// which is only 0 at the end, so scanning loops built on it always make progress.
+    #[inline]
+    pub fn advance_char_or_byte(&mut self) -> u8 {
+        let Some(x) = self.peek() else {
+            return 0;
+        };
+        
+        let width = (UTF8_CHAR_WIDTH[x as usize] as usize).clamp(1, self.remaining_len());
+        unsafe { self.advance_n_unchecked(width) }
+        width as u8
+    }
+    
+    /// Iterates over the consumed bytes backward, from the cursor to the first byte.
+    /// Does not rewind the iterator.
+    #[inline]
+    pub fn iter_consumed_rev(&self) -> impl Iterator<Item = u8> + 'a {
+        self.consumed().iter().rev().copied()
+    }
+    
+    /// Rewinds over the char ending just before the cursor and returns it. Returns `None` without
+    /// rewinding at the first byte or if the bytes before the cursor are not a valid UTF-8 char.
+    #[inline]
+    pub fn rewind_char(&mut self) -> Option<char> {
+        let (c, width) = self.decode_char_back()?;
+        self.cursor = unsafe { self.cursor.sub(width) };
+        Some(c)
+    }
+    
+    /// Decodes the char ending just before the cursor. Returns the char and its width in bytes.
+    #[inline]
+    fn decode_char_back(&self) -> Option<(char, usize)> {
+        let consumed = self.consumed();
+        let mut start = consumed.len().checked_sub(1)?;
+        
+        while start > 0 && consumed.len() - start < 4 && consumed[start] & 0b1100_0000 == 0b1000_0000 {
+            start -= 1;
+        }
+        
+        match decode_char(&consumed[start..]) {
+            Ok((c, width)) if width == consumed.len() - start => Some((c, width)),
+            _ => None,
+        }
+    }
+    
+    /// Consumes `literal` if the remaining bytes start with it. Returns whether it was consumed.
+    #[inline]
+    pub fn consume_slice(&mut self, literal: &[u8]) -> bool {
+        if self.remaining().starts_with(literal) {
+            unsafe { self.advance_n_unchecked(literal.len()) }
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// Consumes `literal`, like [Cursor::consume_slice], but reports where the remaining bytes differ
+    /// from it. Does not advance on error.
+    #[inline]
+    pub fn expect_slice(&mut self, literal: &[u8]) -> Result<(), MismatchError> {
+        let remaining = self.remaining();
+        
+        for (offset, &expected) in literal.iter().enumerate() {
+            let found = remaining.get(offset).copied();
+            
+            if found != Some(expected) {
+                return Err(MismatchError { offset, found });
+            }
+        }
+        
+        unsafe { self.advance_n_unchecked(literal.len()) }
+        Ok(())
+    }
+    
+    /// Gets the next byte. Maps a line terminator that follows `policy` to LF.
+    /// 
+    /// If the cursor is on a line terminator that does not follow `policy`, returns the kind of terminator
+    /// found without advancing. With [LineEnding::Any], this behaves like [Cursor::next_lfn].
+    #[inline]
+    pub fn next_line_ending(&mut self, policy: LineEnding) -> Result<Option<u8>, LineEnding> {
+        let (found, len) = match self.peek() {
+            Some(b'\n') => (LineEnding::Lf, 1),
+            Some(b'\r') if self.peek_n(1) == Some(b'\n') => (LineEnding::CrLf, 2),
+            Some(b'\r') => (LineEnding::Cr, 1),
+            _ => return Ok(self.next()),
+        };
+        
+        if policy != LineEnding::Any && policy != found {
+            return Err(found);
+        }
+        
+        unsafe { self.advance_n_unchecked(len) }
+        Ok(Some(b'\n'))
+    }
+    
+    /// Gets the value of the next UTF-8 sequence as `u32`. Does not advance on error.
+    /// 
+    /// Unlike [Cursor::next_char], only the structure of the sequence is validated, so the value may be
+    /// overlong, a surrogate or greater than `U+10FFFF`.
+    #[inline]
+    pub fn consume_codepoint(&mut self) -> Result<Option<u32>, Error> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        
+        let (code, width) = decode_codepoint(self.remaining())?;
+        unsafe { self.advance_n_unchecked(width) }
+        Ok(Some(code))
+    }
+    
+    /// Checks if the remaining bytes equal `other` exactly.
+    #[inline]
+    pub fn remaining_eq(&self, other: &[u8]) -> bool {
+        self.remaining() == other
+    }
+    
+    /// Skips a `#!` line and its terminator if the cursor is at the first byte. Returns whether a line
+    /// was skipped.
+    #[inline]
+    pub fn skip_shebang(&mut self) -> bool {
+        if self.byte_offset() != 0 || !self.remaining().starts_with(b"#!") {
+            return false;
+        }
+        
+        self.to_line_end();
+        let _ = self.next_lfn();
+        true
+    }
+    
+    /// Counts how many times `byte` repeats at the cursor. Does not advance the iterator.
+    #[inline]
+    pub fn run_length(&self, byte: u8) -> usize {
+        self.count_while(|x| x == byte)
+    }
+    
+    /// Detects the byte order mark at the first byte. Does not advance the iterator.
+    /// Returns `None` if there is no mark or the cursor is not at the first byte.
+    /// 
+    /// UTF-32 marks are checked before UTF-16 marks, because the UTF-16 little endian mark is a prefix of
+    /// the UTF-32 little endian mark.
+    #[inline]
+    pub fn detect_bom(&self) -> Option<Bom> {
+        if self.byte_offset() != 0 {
+            return None;
+        }
+        
+        [Bom::Utf32Le, Bom::Utf32Be, Bom::Utf8, Bom::Utf16Le, Bom::Utf16Be]
+            .into_iter()
+            .find(|bom| self.remaining().starts_with(bom.bytes()))
+    }
+    
+    /// Skips the byte order mark detected by [Cursor::detect_bom] and returns it.
+    #[inline]
+    pub fn skip_detected_bom(&mut self) -> Option<Bom> {
+        let bom = self.detect_bom()?;
+        unsafe { self.advance_n_unchecked(bom.bytes().len()) }
+        Some(bom)
+    }
+    
+    /// Gets the bytes in the whole buffer from index `start` up to index `end`, both as returned by
+    /// [Cursor::index]. Returns `None` if `start > end` or the range is out of bounds.
+    #[inline]
+    pub fn slice_between(&self, start: u64, end: u64) -> Option<&'a [u8]> {
+        let start = self.offset_of(start)?;
+        let end = self.offset_of(end)?;
+        self.as_slice().get(start..end)
+    }
+    
+    /// Skips ASCII whitespace like [Cursor::skip_ascii_whitespace]. Returns the number of bytes skipped
+    /// and the number of line terminators among them, where CR, CRLF and LF each count as one.
+    #[inline]
+    pub fn skip_whitespace_tracking_newlines(&mut self) -> (usize, usize) {
+        let start = self.cursor;
+        let mut newlines = 0;
+        
+        while let Some(x) = self.peek() {
+            match x {
+                b'\n' | b'\r' => {
+                    let _ = self.next_lfn();
+                    newlines += 1;
+                }
+                x if x.is_ascii_whitespace() => unsafe { self.advance_unchecked() },
+                _ => break,
+            }
+        }
+        
+        (unsafe { self.cursor.offset_from_unsigned(start) }, newlines)
+    }
+    
+    /// Turns the remaining bytes into a new cursor, whose first byte is the current position and whose
+    /// index starts at 0. The tab width and the name are kept.
+    #[inline]
+    pub fn into_remaining(self) -> Cursor<'a> {
+        let mut cursor = Cursor::new(self.remaining());
+        cursor.tab_width = self.tab_width;
+        cursor.name = self.name;
+        cursor
+    }
+    
+    /// Consumes a C-style identifier, matching `[A-Za-z_][A-Za-z0-9_]*`, and returns it.
+    /// Returns `None` without advancing if the next byte cannot start an identifier.
+    #[inline]
+    pub fn consume_c_identifier(&mut self) -> Option<&'a str> {
+        if !matches!(self.peek(), Some(b'A'..=b'Z' | b'a'..=b'z' | b'_')) {
+            return None;
+        }
+        
+        let mut recorder = self.begin_recording();
+        unsafe { recorder.advance_unchecked() }
+        
+        while let Some(b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_') = recorder.peek() {
+            unsafe { recorder.advance_unchecked() }
+        }
+        
+        // SAFETY: The identifier is ASCII.
+        Some(unsafe { core::str::from_utf8_unchecked(recorder.stop()) })
+    }
+    
+    /// Counts the remaining chars, validating them as UTF-8. Does not advance the iterator.
+    /// 
+    /// On malformed UTF-8, returns the index of the first invalid char together with the error.
+    #[inline]
+    pub fn count_chars(&self) -> Result<usize, (u64, Error)> {
+        let remaining = self.remaining();
+        let mut count = 0;
+        let mut i = 0;
+        
+        while i < remaining.len() {
+            if remaining[i].is_ascii() {
+                i += 1;
+            } else {
+                match decode_char(&remaining[i..]) {
+                    Ok((_, width)) => i += width,
+                    Err(e) => return Err((self.index_at(self.byte_offset() + i), e)),
+                }
+            }
+            count += 1;
+        }
+        
+        Ok(count)
+    }
+    
+    /// Passes the remaining bytes to `f`, which returns how many of them it matches. Does not advance
+    /// the iterator. Returns `None` if `f` does, or if the count exceeds the remaining bytes.
+    #[inline]
+    pub fn peek_matches<F: FnOnce(&[u8]) -> Option<usize>>(&self, f: F) -> Option<usize> {
+        f(self.remaining()).filter(|&n| n <= self.remaining_len())
+    }
+    
+    /// Like [Cursor::peek_matches], but advances by the matched count.
+    #[inline]
+    pub fn consume_matches<F: FnOnce(&[u8]) -> Option<usize>>(&mut self, f: F) -> Option<usize> {
+        let n = self.peek_matches(f)?;
+        unsafe { self.advance_n_unchecked(n) }
+        Some(n)
+    }
+    
+    /// Shrinks the input to end `len` bytes after the cursor. Returns the previous end to pass to
+    /// [Cursor::restore_end], or `None` if fewer than `len` bytes remain.
+    #[inline]
+    pub fn set_end(&mut self, len: usize) -> Option<PrevEnd> {
+        if len > self.remaining_len() {
+            return None;
+        }
+        
+        let prev = PrevEnd { end: self.end };
+        self.end = unsafe { self.cursor.add(len) };
+        Some(prev)
+    }
+    
+    /// Restores the end of the input that [Cursor::set_end] replaced.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `prev` is before the cursor or past the end of the buffer, which can only happen if it
+    /// was returned for another cursor.
+    #[inline]
+    pub fn restore_end(&mut self, prev: PrevEnd) {
+        assert!(
+            self.cursor <= prev.end && prev.end <= self.limit,
+            "the end does not belong to this cursor",
+        );
+        self.end = prev.end;
+    }
+    
+    /// Consumes a line terminator (CR, CRLF or LF) if the cursor is on one. Returns whether one was consumed.
+    #[inline]
+    pub fn consume_newline(&mut self) -> bool {
+        match self.peek() {
+            Some(b'\n' | b'\r') => {
+                let _ = self.next_lfn();
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Consumes the next `N` bytes and returns them as an array, or `None` without advancing if fewer
+    /// than `N` bytes remain.
+    #[inline]
+    pub fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let array = *self.remaining().first_chunk::<N>()?;
+        unsafe { self.advance_n_unchecked(N) }
+        Some(array)
+    }
+    
+    /// Consumes a floating-point literal and parses it. The literal is an optional sign, digits, an
+    /// optional fraction of `.` and digits, and an optional exponent of `e` or `E`, an optional sign and
+    /// digits. A `.` or exponent marker that is not followed by digits is not consumed.
+    /// 
+    /// Returns `None` without advancing if there are no digits at the cursor.
+    #[inline]
+    pub fn consume_f64(&mut self) -> Option<f64> {
+        let start = self.cursor;
+        
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            unsafe { self.advance_unchecked() }
+        }
+        
+        if self.skip_ascii_digits() == 0 {
+            self.cursor = start;
+            return None;
+        }
+        
+        if self.peek() == Some(b'.') && matches!(self.peek_n(1), Some(b'0'..=b'9')) {
+            unsafe { self.advance_unchecked() }
+            self.skip_ascii_digits();
+        }
+        
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let exponent = self.cursor;
+            unsafe { self.advance_unchecked() }
+            
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                unsafe { self.advance_unchecked() }
+            }
+            
+            if self.skip_ascii_digits() == 0 {
+                self.cursor = exponent;
+            }
+        }
+        
+        // SAFETY: The literal is ASCII.
+        unsafe { core::str::from_utf8_unchecked(self.slice_from(start)) }.parse().ok()
+    }
+    
+    /// Skips a comment that starts with `prefix` and runs to the end of the line. The line terminator is
+    /// not consumed. Returns whether a comment was skipped.
+    #[inline]
+    pub fn skip_line_comment(&mut self, prefix: &[u8]) -> bool {
+        if !self.consume_slice(prefix) {
+            return false;
+        }
+        
+        self.to_line_end();
+        true
+    }
+    
+    /// Skips a comment from `open` to `close`. If `nested` is set, comments inside it must be closed as
+    /// well. Returns whether a comment was skipped. Does not advance on error.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `open` or `close` is empty.
+    #[inline]
+    pub fn skip_block_comment(&mut self, open: &[u8], close: &[u8], nested: bool) -> Result<bool, CommentError> {
+        assert!(!open.is_empty() && !close.is_empty(), "comment delimiters must not be empty");
+        
+        let start = self.cursor;
+        
+        if !self.consume_slice(open) {
+            return Ok(false);
+        }
+        
+        let mut depth = 1usize;
+        
+        while self.has_next() {
+            if self.consume_slice(close) {
+                depth -= 1;
+                
+                if depth == 0 {
+                    return Ok(true);
+                }
+            } else if nested && self.consume_slice(open) {
+                depth += 1;
+            } else {
+                unsafe { self.advance_unchecked() }
+            }
+        }
+        
+        self.cursor = start;
+        Err(CommentError::Unterminated(self.index()))
+    }
+    
+    /// Consumes all remaining bytes and returns them.
+    #[inline]
+    pub fn consume_rest(&mut self) -> &'a [u8] {
+        let rest = self.remaining();
+        self.cursor = self.end;
+        rest
+    }
+    
+    /// Saves the position of the cursor.
+    #[inline]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            cursor: self.cursor,
+            first: self.first,
+        }
+    }
+    
+    /// Moves the cursor back (or forward) to a saved position. The index and [Cursor::line_column] are
+    /// derived from the position, so they are restored as well.
+    /// 
+    /// The checkpoint may come from a [Cursor::fork] of this cursor, which brings this cursor to where the
+    /// fork got.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the checkpoint is outside the input, which can only happen if it was created by another
+    /// cursor or before [Cursor::set_end]. In debug builds, also panics if the checkpoint was created by a
+    /// cursor that does not start at the same byte, which is not a fork.
+    #[inline]
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        assert!(
+            self.first <= checkpoint.cursor && checkpoint.cursor <= self.end,
+            "the checkpoint is outside the input",
+        );
+        debug_assert!(self.first == checkpoint.first, "the checkpoint belongs to another cursor");
+        self.cursor = checkpoint.cursor;
+    }
+    
+    /// Calls `f` until it returns `None` and collects the results.
+    /// 
+    /// Every call that returns `Some` must advance the cursor, otherwise the loop would never end. If a call
+    /// does not, this panics in debug builds to point at the bug, and in release builds keeps the result
+    /// and stops.
+    #[cfg(feature = "alloc")]
+    pub fn repeat<T>(&mut self, mut f: impl FnMut(&mut Self) -> Option<T>) -> Vec<T> {
+        let mut results = Vec::new();
+        
+        loop {
+            let before = self.checkpoint();
+            
+            let Some(result) = f(self) else {
+                break;
+            };
+            results.push(result);
+            
+            if self.checkpoint() <= before {
+                debug_assert!(false, "repeat made no progress");
+                break;
+            }
+        }
+        
+        results
+    }
+    
+    /// Advances until a byte in `set` is found and returns it, leaving the cursor on it. Returns `None`
+    /// after consuming everything if no byte in `set` is found.
+    /// 
+    /// Small sets are checked linearly per byte; larger sets are turned into a table for
+    /// [Cursor::consume_until_class].
+    #[inline]
+    pub fn consume_until_any(&mut self, set: &[u8]) -> Option<u8> {
+        if set.len() > 4 {
+            let mut class = [false; 256];
+            
+            for &x in set {
+                class[x as usize] = true;
+            }
+            
+            self.consume_until_class(&class);
+            return self.peek();
+        }
+        
+        while let Some(x) = self.peek() {
+            if set.contains(&x) {
+                return Some(x);
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        None
+    }
+    
+    /// Annotates an error from a sub-parser with the current index, next byte, line and column, and the
+    /// name of the input.
+    #[inline]
+    pub fn with_position<T>(&self, r: Result<T, Error>) -> Result<T, PositionedError<'a>> {
+        r.map_err(|error| PositionedError {
+            error,
+            index: self.index(),
+            found: self.peek(),
+            line_column: self.line_column(),
+            name: self.name,
+        })
+    }
+    
+    /// Checks if the cursor is at the start of a UTF-8 char, that is the next byte is not a continuation
+    /// byte or the cursor is at the end.
+    #[inline]
+    pub fn is_at_char_boundary(&self) -> bool {
+        self.peek().is_none_or(|x| x & 0b1100_0000 != 0b1000_0000)
+    }
+    
+    /// Runs `f` while recording and returns its result together with the text it consumed.
+    /// The recording always ends when `f` returns.
+    /// 
+    /// Unlike [Cursor::scan], `f` cannot fail, so there is no way to hand back invalid text.
+    /// Use [Cursor::begin_recording] directly if the input may not be valid UTF-8.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the consumed bytes are not valid UTF-8.
+    #[inline]
+    pub fn recorded_by<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> (R, &'a str) {
+        let mut recorder = self.begin_recording();
+        let result = f(&mut recorder);
+        
+        match core::str::from_utf8(recorder.stop()) {
+            Ok(text) => (result, text),
+            Err(_) => panic!("recorded bytes are not valid UTF-8"),
+        }
+    }
+    
+    /// Consumes digits in `radix` that may be grouped by `separator` bytes, and returns their value.
+    /// Letters of either case are digits from 10 on.
+    /// 
+    /// A separator is only accepted between two digits, so the run must start and end with a digit and
+    /// must not contain two adjacent separators: `1_000` is accepted, `_1`, `1_` and `1__0` are not. The
+    /// run ends at the first byte that is neither a digit nor a separator.
+    /// 
+    /// Returns `None` without advancing if there are no digits, a separator is misplaced or the value
+    /// overflows `u64`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `radix` is not in the range from 2 to 36 or `separator` is a digit in `radix`.
+    #[inline]
+    pub fn consume_u64_separated(&mut self, separator: u8, radix: u32) -> Option<u64> {
+        assert!((2..=36).contains(&radix), "radix must be in the range from 2 to 36");
+        assert!((separator as char).to_digit(radix).is_none(), "the separator must not be a digit");
+        
+        let start = self.cursor;
+        let mut value: Option<u64> = Some(0);
+        let mut after_digit = false;
+        
+        loop {
+            match self.peek() {
+                Some(x) if x == separator && after_digit => after_digit = false,
+                Some(x) if x == separator => break,
+                Some(x) => match (x as char).to_digit(radix) {
+                    Some(digit) => {
+                        value = value
+                            .and_then(|v| v.checked_mul(radix as u64))
+                            .and_then(|v| v.checked_add(digit as u64));
+                        after_digit = true;
+                    }
+                    None => break,
+                },
+                None => break,
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        if !after_digit || value.is_none() {
+            self.cursor = start;
+            return None;
+        }
+        
+        value
+    }
+    
+    /// Gets how many bytes of the next char are present, which is less than the width announced by its
+    /// first byte if the input ends within the char. Continuation bytes are not validated.
+    /// 
+    /// Returns `None` at the end or if the next byte cannot start a char.
+    #[inline]
+    pub fn available_char_len(&self) -> Option<u8> {
+        let width = UTF8_CHAR_WIDTH[self.peek()? as usize] as usize;
+        
+        if width == 0 {
+            return None;
+        }
+        
+        Some(width.min(self.remaining_len()) as u8)
+    }
+    
+    /// Consumes the maximal run of ASCII digits and returns it, which is empty if the next byte is not a
+    /// digit. Meant for number literals that are parsed elsewhere, like big integers.
+    #[inline]
+    pub fn consume_ascii_digits(&mut self) -> &'a str {
+        let start = self.cursor;
+        self.skip_ascii_digits();
+        unsafe { core::str::from_utf8_unchecked(self.slice_from(start)) }
+    }
+    
+    /// Consumes the next char if it is `expected`. Does not advance on error.
+    #[inline]
+    pub fn expect_char(&mut self, expected: char) -> Result<(), ExpectCharError> {
+        if !self.has_next() {
+            return Err(ExpectCharError::End(self.index()));
+        }
+        
+        match decode_char(self.remaining()) {
+            Ok((c, width)) if c == expected => {
+                unsafe { self.advance_n_unchecked(width) }
+                Ok(())
+            }
+            Ok((c, _)) => Err(ExpectCharError::Mismatch(c, self.index())),
+            Err(error) => Err(ExpectCharError::Invalid(error, self.index())),
+        }
+    }
+    
+    /// Gets the number of bytes the next `n` chars occupy, validating them as UTF-8. Does not advance the
+    /// iterator.
+    /// 
+    /// Returns [Error::Incomplete] if fewer than `n` chars remain, like [Cursor::peek_chars].
+    #[inline]
+    pub fn byte_len_of_next_chars(&self, n: usize) -> Result<usize, Error> {
+        let remaining = self.remaining();
+        let mut len = 0;
+        
+        for _ in 0..n {
+            match decode_char(&remaining[len..]) {
+                Ok((_, width)) => len += width,
+                Err(e) if e == Error::Empty || e.is_missing() => return Err(Error::Incomplete),
+                Err(e) => return Err(e),
+            }
+        }
+        
+        Ok(len)
+    }
+    
+    /// Consumes all remaining bytes, folding each into an accumulator that starts as `init`, and returns
+    /// the accumulator. Meant for checksums that are computed while parsing.
+    #[inline]
+    pub fn fold_bytes<B>(&mut self, init: B, f: impl FnMut(B, u8) -> B) -> B {
+        self.consume_rest().iter().copied().fold(init, f)
+    }
+    
+    /// Skips the run of continuation bytes at the cursor and returns its length. Meant for resynchronizing
+    /// after a decoding error; afterward [Cursor::is_at_char_boundary] holds.
+    #[inline]
+    pub fn skip_continuation_bytes(&mut self) -> usize {
+        let start = self.cursor;
+        
+        while let Some(0b1000_0000..=0b1011_1111) = self.peek() {
+            unsafe { self.advance_unchecked() }
+        }
+        
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Gets the number of bytes between the start of the current line and the cursor, like the column of
+    /// [Cursor::line_column] minus 1 but counting bytes. A line starts after a `\n` or `\r`, or at the
+    /// first byte.
+    #[inline]
+    pub fn column_bytes(&self) -> usize {
+        let consumed = self.consumed();
+        
+        match consumed.iter().rposition(|&x| x == b'\n' || x == b'\r') {
+            Some(i) => consumed.len() - (i + 1),
+            None => consumed.len(),
+        }
+    }
+    
+    /// Peeks into the next char decoded as UTF-8, or U+FFFD if the next bytes are malformed. Does not
+    /// advance the iterator.
+    /// 
+    /// To stay in sync, advance by the UTF-8 width of a valid char but by a single byte after a
+    /// replacement, so every byte of a malformed sequence yields its own U+FFFD.
+    #[inline]
+    pub fn peek_char_lossy(&self) -> Option<char> {
+        if !self.has_next() {
+            return None;
+        }
+        
+        Some(decode_char(self.remaining()).map_or(char::REPLACEMENT_CHARACTER, |(c, _)| c))
+    }
+    
+    /// Advances while `class[byte]` is `true`, like [Cursor::consume_class], and returns the consumed
+    /// bytes. The class is meant for ASCII bytes only: non-ASCII bytes always end the run, which keeps
+    /// the result valid UTF-8.
+    #[inline]
+    pub fn consume_class_str(&mut self, class: &[bool; 256]) -> &'a str {
+        let start = self.cursor;
+        
+        while let Some(x) = self.peek() {
+            if !x.is_ascii() || !class[x as usize] {
+                break;
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        unsafe { core::str::from_utf8_unchecked(self.slice_from(start)) }
+    }
+    
+    /// Creates an independent copy of the cursor for speculative parsing. Its checkpoints can be restored
+    /// on this cursor.
+    #[inline]
+    pub fn fork(&self) -> Cursor<'a> {
+        self.clone()
+    }
+    
+    /// Iterates over the remaining bytes in chunks of `size` bytes, consuming each chunk as it is yielded.
+    /// The last chunk is shorter if the remaining length is not divisible by `size`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn chunks<'b>(&'b mut self, size: usize) -> Chunks<'a, 'b> {
+        assert!(size != 0, "the chunk size must not be 0");
+        Chunks { cursor: self, size }
+    }
+    
+    /// Peeks into the line at the cursor, without its terminator (CR, CRLF or LF), validated as UTF-8.
+    /// Returns `None` at the end. Does not advance the iterator.
+    #[inline]
+    pub fn peek_line(&self) -> Result<Option<&'a str>, Utf8Error> {
+        if !self.has_next() {
+            return Ok(None);
+        }
+        
+        let remaining = self.remaining();
+        let len = remaining.iter().position(|&x| x == b'\n' || x == b'\r').unwrap_or(remaining.len());
+        core::str::from_utf8(&remaining[..len]).map(Some)
+    }
+    
+    /// Validates the remaining bytes as UTF-8 and advances to the end. Returns the number of chars.
+    /// Runs of ASCII are checked eight bytes at a time, so this is faster than advancing char by char.
+    /// 
+    /// On malformed UTF-8, advances to the first invalid char and returns its index together with the
+    /// error. Like in [Cursor::count_chars], this is the [Cursor::index] of the char, which is only its
+    /// byte offset if the index was not rebased; call [Cursor::byte_offset] afterwards for the latter.
+    #[inline]
+    pub fn validate_and_advance_all(&mut self) -> Result<usize, (u64, Error)> {
+        const HIGH_BITS: u64 = u64::from_ne_bytes([0x80; 8]);
+        
+        let remaining = self.remaining();
+        let mut count = 0;
+        let mut i = 0;
+        
+        while i < remaining.len() {
+            if let Some(word) = remaining[i..].first_chunk::<8>() {
+                if u64::from_ne_bytes(*word) & HIGH_BITS == 0 {
+                    i += 8;
+                    count += 8;
+                    continue;
+                }
+            }
+            
+            if remaining[i].is_ascii() {
+                i += 1;
+            } else {
+                match decode_char(&remaining[i..]) {
+                    Ok((_, width)) => i += width,
+                    Err(e) => {
+                        unsafe { self.advance_n_unchecked(i) }
+                        return Err((self.index(), e));
+                    }
+                }
+            }
+            count += 1;
+        }
+        
+        self.cursor = self.end;
+        Ok(count)
+    }
+    
+    /// Consumes the spaces and tabs at the cursor, which is meant to be at a line start, and returns the
+    /// indentation in columns.
+    /// 
+    /// Columns are counted from 0 at the cursor. A space adds one column and a tab advances to the next
+    /// multiple of `tab_width`, so with a width of 4 both `"  \t"` and `"\t"` indent by 4 columns, and
+    /// `"\t  "` by 6. A width of 0 or 1 counts a tab as a single column, like [Cursor::set_tab_width].
+    #[inline]
+    pub fn consume_indent(&mut self, tab_width: u8) -> usize {
+        let tab_width = tab_width.max(1) as usize;
+        let mut columns = 0;
+        
+        loop {
+            match self.peek() {
+                Some(b' ') => columns += 1,
+                Some(b'\t') => columns = (columns / tab_width + 1) * tab_width,
+                _ => break,
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        columns
+    }
+    
+    /// Turns the cursor into its whole buffer as a string slice, validating it as UTF-8. Like
+    /// [Cursor::as_str], but marks the end of parsing.
+    #[inline]
+    pub fn try_into_str(self) -> Result<&'a str, Utf8Error> {
+        self.as_str()
+    }
+    
+    /// Advances to the start of the next line, that is past the next line terminator (CR, CRLF or LF),
+    /// or to the end. Returns the number of bytes skipped. Meant for recovering from a parse error.
+    #[inline]
+    pub fn recover_to_next_line(&mut self) -> usize {
+        let start = self.cursor;
+        self.to_line_end();
+        let _ = self.next_lfn();
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Consumes a region from the `open` byte at the cursor to its matching `close` byte, like
+    /// [Cursor::take_balanced], but an `escape` byte is skipped together with the byte after it, so
+    /// escaped delimiters neither nest nor close. Returns the bytes between the delimiters with the
+    /// escapes left in. Does not advance on error.
+    #[inline]
+    pub fn consume_template(&mut self, open: u8, close: u8, escape: u8) -> Result<&'a [u8], TemplateError> {
+        if self.peek() != Some(open) {
+            return Err(TemplateError::MissingOpen);
+        }
+        
+        let start = self.cursor;
+        let mut depth = 1usize;
+        unsafe { self.advance_unchecked() }
+        
+        while let Some(x) = self.next() {
+            if x == escape {
+                if self.next().is_none() {
+                    break;
+                }
+            } else if x == close {
+                depth -= 1;
+                
+                if depth == 0 {
+                    let region = unsafe { self.slice_from(start) };
+                    return Ok(&region[1..region.len() - 1]);
+                }
+            } else if x == open {
+                depth += 1;
+            }
+        }
+        
+        self.cursor = start;
+        Err(TemplateError::Unterminated(self.index()))
+    }
+    
+    /// Counts how often each byte value occurs in the remaining bytes. Does not advance the iterator.
+    /// Meant for heuristics like telling binary input from text.
+    #[inline]
+    pub fn byte_histogram(&self) -> [u32; 256] {
+        // Four tables so that runs of the same byte do not serialize on one counter.
+        let mut tables = [[0u32; 256]; 4];
+        let mut chunks = self.remaining().chunks_exact(4);
+        
+        for chunk in &mut chunks {
+            for (table, &x) in tables.iter_mut().zip(chunk) {
+                table[x as usize] += 1;
+            }
+        }
+        
+        for &x in chunks.remainder() {
+            tables[0][x as usize] += 1;
+        }
+        
+        let mut histogram = tables[0];
+        
+        for table in &tables[1..] {
+            for (count, &other) in histogram.iter_mut().zip(table) {
+                *count += other;
+            }
+        }
+        
+        histogram
+    }
+    
+    /// Advances while `pred` holds, but at most `max` bytes. Returns the number of bytes consumed, or
+    /// `Err(max)` if `pred` still holds after `max` bytes, in which case the cursor is left `max` bytes
+    /// ahead. Meant for bounding the length of tokens.
+    #[inline]
+    pub fn consume_while_max<F: FnMut(u8) -> bool>(&mut self, max: usize, mut pred: F) -> Result<usize, usize> {
+        let mut len = 0;
+        
+        while let Some(x) = self.peek() {
+            if !pred(x) {
+                break;
+            }
+            
+            if len == max {
+                return Err(max);
+            }
+            
+            unsafe { self.advance_unchecked() }
+            len += 1;
+        }
+        
+        Ok(len)
+    }
+    
+    /// Peeks into the char ending just before the cursor. Does not rewind the iterator. Returns `None` at
+    /// the first byte or if the bytes before the cursor are not a valid UTF-8 char.
+    #[inline]
+    pub fn peek_back_char(&self) -> Option<char> {
+        self.decode_char_back().map(|(c, _)| c)
+    }
+    
+    /// Consumes the longest keyword of `tree` the remaining bytes start with and returns its id. Unlike
+    /// with [crate::match_keyword!], keywords match as plain prefixes, without a word boundary check.
+    /// Returns `None` without advancing if none matches.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn consume_prefix_tree(&mut self, tree: &PrefixTree) -> Option<u32> {
+        let (id, len) = tree.longest_match(self.remaining())?;
+        unsafe { self.advance_n_unchecked(len) }
+        Some(id)
+    }
+    
+    /// Consumes a CRLF sequence, rejecting lone CR and LF terminators as protocols like HTTP require.
+    /// Does not advance on error.
+    #[inline]
+    pub fn consume_crlf(&mut self) -> Result<(), CrlfError> {
+        match self.remaining() {
+            [b'\r', b'\n', ..] => {}
+            [b'\r', rest @ ..] => return Err(CrlfError::LoneCr(rest.first().copied())),
+            [b'\n', ..] => return Err(CrlfError::LoneLf),
+            rest => return Err(CrlfError::Missing(rest.first().copied())),
+        }
+        
+        unsafe { self.advance_n_unchecked(2) }
+        Ok(())
+    }
+    
+    /// Gets the offset of the cursor within `parent`, for a cursor created from a subslice of it. Returns
+    /// `None` if the input of the cursor does not start within `parent` or the cursor is past its end.
+    #[inline]
+    pub fn offset_within(&self, parent: &[u8]) -> Option<usize> {
+        let parent = parent.as_ptr_range();
+        let (start, end) = (parent.start as usize, parent.end as usize);
+        
+        if (start..=end).contains(&(self.first as usize)) && self.cursor as usize <= end {
+            Some(self.cursor as usize - start)
+        } else {
+            None
+        }
+    }
+    
+    /// Consumes the next byte if it is in `set` and returns it. Does not advance on error.
+    #[inline]
+    pub fn expect_one_of<'s>(&mut self, set: &'s [u8]) -> Result<u8, ExpectError<'s>> {
+        match self.peek() {
+            Some(x) if set.contains(&x) => {
+                unsafe { self.advance_unchecked() }
+                Ok(x)
+            }
+            found => Err(ExpectError { found, expected: set }),
+        }
+    }
+    
+    /// Gets the line and column of each index, as returned by [Cursor::index] and reported in errors, both
+    /// starting at 1 and counted like [Cursor::line_column]. Indices before the first byte resolve to the
+    /// first byte, and indices past the end resolve to the end.
+    /// 
+    /// The inputs are [Cursor::index] values, not byte offsets from the first byte. The two only agree if
+    /// the cursor was not created with [Cursor::new_with_base] and [Cursor::reset_index] was never called.
+    /// 
+    /// The buffer is scanned once if the indices are sorted. An index smaller than its predecessor
+    /// restarts the scan from the first byte.
+    #[cfg(feature = "alloc")]
+    pub fn resolve_positions(&self, indices: &[u64]) -> Vec<(u32, u32)> {
+        let len = self.as_slice().len();
+        let mut positions = Vec::with_capacity(indices.len());
+        let (mut line, mut column, mut scanned) = (1, 0, 0);
+        
+        for &index in indices {
+            let offset = match self.offset_of(index) {
+                Some(offset) => offset.min(len),
+                None if index < self.index_origin => 0,
+                None => len,
+            };
+            
+            if offset < scanned {
+                (line, column, scanned) = (1, 0, 0);
+            }
+            
+            self.scan_line_column(&mut line, &mut column, scanned..offset);
+            scanned = offset;
+            positions.push((line, column + 1));
+        }
+        
+        positions
+    }
+    
+    /// Advances while `pred` holds, passing `state` to each call so the condition can depend on the bytes
+    /// before. Returns the number of bytes consumed.
+    #[inline]
+    pub fn consume_while_stateful<S>(&mut self, state: &mut S, mut pred: impl FnMut(&mut S, u8) -> bool) -> usize {
+        let start = self.cursor;
+        
+        while let Some(x) = self.peek() {
+            if !pred(state, x) {
+                break;
+            }
+            unsafe { self.advance_unchecked() }
+        }
+        
+        unsafe { self.cursor.offset_from_unsigned(start) }
+    }
+    
+    /// Asserts the invariants of the cursor in debug builds: `first <= cursor <= end <= limit`, the
+    /// index origin lies within the buffer and the index maps back to the position of the cursor. Does
+    /// nothing in release builds.
+    #[inline]
+    pub fn check_invariants(&self) {
+        debug_assert!(self.first <= self.cursor, "the cursor is before the first byte");
+        debug_assert!(self.cursor <= self.end, "the cursor is past the end");
+        debug_assert!(self.end <= self.limit, "the end is past the limit");
+        debug_assert!(
+            self.index_origin_offset <= unsafe { self.limit.offset_from_unsigned(self.first) },
+            "the index origin is outside the buffer",
+        );
+        debug_assert_eq!(self.offset_of(self.index()), Some(self.byte_offset()), "the index is out of sync");
+    }
+    
+    /// Consumes an escape sequence after a backslash, which must already be consumed, and returns the
+    /// char it stands for. Does not advance on error.
+    /// 
+    /// The escapes are `\n`, `\t`, `\r`, `\\`, `\"`, `\'` and `\0`, `\xHH` with two hex digits for a
+    /// value up to `0x7F`, and `\u{H}` with one to six hex digits for a Unicode scalar value.
+    #[inline]
+    pub fn consume_escape(&mut self) -> Result<char, EscapeError> {
+        let start = self.cursor;
+        let result = self.consume_escape_inner();
+        
+        if result.is_err() {
+            self.cursor = start;
+        }
+        
+        result
+    }
+    
+    /// Consumes an escape sequence for [Cursor::consume_escape], which rewinds on error.
+    #[inline]
+    fn consume_escape_inner(&mut self) -> Result<char, EscapeError> {
+        let hex_digit = |x: u8| (x as char).to_digit(16);
+        
+        match self.next().ok_or(EscapeError::End)? {
+            b'n' => Ok('\n'),
+            b't' => Ok('\t'),
+            b'r' => Ok('\r'),
+            b'\\' => Ok('\\'),
+            b'"' => Ok('"'),
+            b'\'' => Ok('\''),
+            b'0' => Ok('\0'),
+            b'x' => {
+                let [high, low] = self.read_array::<2>().ok_or(EscapeError::End)?;
+                let (Some(high), Some(low)) = (hex_digit(high), hex_digit(low)) else {
+                    return Err(EscapeError::InvalidHex);
+                };
+                let value = high << 4 | low;
+                
+                if value > 0x7F {
+                    return Err(EscapeError::OutOfRange(value));
+                }
+                
+                Ok(value as u8 as char)
+            }
+            b'u' => {
+                match self.next() {
+                    Some(b'{') => {}
+                    Some(_) => return Err(EscapeError::InvalidHex),
+                    None => return Err(EscapeError::End),
+                }
+                
+                let mut value = 0u32;
+                let mut digits = 0;
+                
+                while let Some(digit) = self.peek().and_then(hex_digit) {
+                    if digits == 6 {
+                        return Err(EscapeError::InvalidHex);
+                    }
+                    value = value << 4 | digit;
+                    digits += 1;
+                    unsafe { self.advance_unchecked() }
+                }
+                
+                match self.next() {
+                    Some(b'}') if digits > 0 => {}
+                    Some(_) => return Err(EscapeError::InvalidHex),
+                    None => return Err(EscapeError::End),
+                }
+                
+                char::from_u32(value).ok_or(EscapeError::OutOfRange(value))
+            }
+            x => Err(EscapeError::Unknown(x)),
+        }
+    }
+    
+    /// Skips spaces, tabs, form feeds and vertical tabs, but not line terminators, unlike
+    /// [Cursor::skip_ascii_whitespace]. Returns the number of bytes skipped.
+    #[inline]
+    pub fn skip_inline_whitespace(&mut self) -> usize {
+        self.consume_class(&INLINE_WHITESPACE)
+    }
+    
+    /// Consumes `open`, which must be at the cursor, and everything up to and including the next `close`,
+    /// and returns the bytes between them. Delimiters do not nest, so this suits regions like `<!-- -->`
+    /// and `/* */`. Does not advance on error.
+    #[inline]
+    pub fn consume_delimited(&mut self, open: &[u8], close: &[u8]) -> Result<&'a [u8], DelimError> {
+        let Some(rest) = self.remaining().strip_prefix(open) else {
+            return Err(DelimError::MissingOpen);
+        };
+        
+        let Some(len) = (0..=rest.len()).find(|&i| rest[i..].starts_with(close)) else {
+            return Err(DelimError::Unterminated(self.index()));
+        };
+        
+        unsafe { self.advance_n_unchecked(open.len() + len + close.len()) }
+        Ok(&rest[..len])
+    }
+    
+    /// Checks if the remaining bytes start with `needle`, ignoring the case of ASCII letters. Other bytes,
+    /// including all non-ASCII bytes, must match exactly. Does not advance the iterator.
+    #[inline]
+    pub fn remaining_starts_with_ignore_ascii_case(&self, needle: &[u8]) -> bool {
+        self.remaining().get(..needle.len()).is_some_and(|x| x.eq_ignore_ascii_case(needle))
+    }
+    
+    /// Advances over chars while `pred` holds and returns them, like for identifiers with Unicode
+    /// classes. On malformed UTF-8, stops at the invalid char and returns the error.
+    #[inline]
+    pub fn consume_while_char_str(&mut self, mut pred: impl FnMut(char) -> bool) -> Result<&'a str, Error> {
+        let start = self.cursor;
+        
+        while self.has_next() {
+            let (c, width) = decode_char(self.remaining())?;
+            
+            if !pred(c) {
+                break;
+            }
+            unsafe { self.advance_n_unchecked(width) }
+        }
+        
+        // SAFETY: Only valid chars were consumed.
+        Ok(unsafe { core::str::from_utf8_unchecked(self.slice_from(start)) })
+    }
+    
+    /// Rewinds over up to `n` chars and returns how many were rewound, which is less than `n` only at the
+    /// first byte. Each char is found by stepping back over continuation bytes to the byte before them,
+    /// but over at most four bytes, so malformed input is not validated but still makes progress.
+    #[inline]
+    pub fn rewind_n_chars(&mut self, n: usize) -> usize {
+        for count in 0..n {
+            if !self.can_rewind() {
+                return count;
+            }
+            
+            for _ in 0..4 {
+                unsafe { self.rewind_unchecked() }
+                
+                if !self.can_rewind() || unsafe { self.peek_unchecked() } & 0b1100_0000 != 0b1000_0000 {
+                    break;
+                }
+            }
+        }
+        
+        n
+    }
+    
+    /// Borrows the cursor mutably through method-call auto-referencing, so [crate::match_keyword!]
+    /// evaluates its cursor expression once, whether it is a cursor, a `&mut Cursor` or a [Recorder].
+    #[doc(hidden)]
+    #[inline]
+    pub fn __as_mut(&mut self) -> &mut Self {
+        self
+    }
+}
+
+impl<'a> Recorder<'a, '_> {
+    /// Stops the recording and returns the recorded bytes.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the cursor was moved before the first recorded byte.
+    #[inline]
+    pub fn stop(self) -> &'a [u8] {
+        self.current_slice()
+    }
+    
+    /// Gets the bytes recorded so far without stopping the recording. The buffer is immutable, so the
+    /// slice stays valid for `'a` however the cursor moves afterward.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if the cursor was moved before the first recorded byte.
+    #[inline]
+    pub fn current_slice(&self) -> &'a [u8] {
+        assert!(self.start <= self.cursor.cursor, "the cursor is before the recording");
+        unsafe { self.cursor.slice_from(self.start) }
+    }
+    
+    /// Stops the recording and rewinds the cursor to the first recorded byte. The index is rewound
+    /// with it, also over CRLF sequences that [Cursor::next_lfn] collapsed.
+    #[inline]
+    pub fn rewind(self) {
+        self.cursor.cursor = self.start;
+    }
+}
+
+impl<'a> Deref for Recorder<'a, '_> {
+    type Target = Cursor<'a>;
+    
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.cursor
+    }
+}
+
+impl DerefMut for Recorder<'_, '_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.cursor
+    }
+}
+
+impl<'a> Iterator for Chunks<'a, '_> {
+    type Item = &'a [u8];
+    
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.cursor.has_next() {
+            return None;
+        }
+        
+        let start = self.cursor.cursor;
+        unsafe {
+            self.cursor.advance_n_unchecked(self.size.min(self.cursor.remaining_len()));
+            Some(self.cursor.slice_from(start))
+        }
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.cursor.remaining_len().div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+/// Matches the longest keyword at the cursor, consumes it and evaluates the corresponding arm.
+/// 
+/// Keywords must be non-empty byte string literals, which is checked at compile time. Candidates are
+/// tried by length, longest first, so `b"int"` wins over `b"in"`. A keyword that ends in a word byte
+/// (an ASCII letter, ASCII digit or `_`, like for [Cursor::at_word_boundary]) only matches if the next
+/// byte is not a word byte, so `b"if"` does not match `iffy`. If no keyword matches, the fallback arm is
+/// evaluated with the cursor not advanced.
+#[macro_export]
+macro_rules! match_keyword {
+    ($cursor:expr, { $($keyword:literal => $arm:expr),+ , _ => $fallback:expr $(,)? }) => {{
+        const KEYWORDS: &[&[u8]] = &[$($keyword),+];
+        const MAX_LEN: usize = $crate::bytes::__max_keyword_len(KEYWORDS);
+        
+        let cursor = $cursor.__as_mut();
+        let matched: Option<&[u8]> = 'matched: {
+            let remaining = cursor.remaining();
+            let mut len = if remaining.len() < MAX_LEN { remaining.len() } else { MAX_LEN };
+            
+            while len > 0 {
+                match &remaining[..len] {
+                    $($keyword)|+ if $crate::bytes::__is_keyword_end(remaining, len) => {
+                        break 'matched Some(&remaining[..len])
+                    }
+                    _ => len -= 1,
+                }
+            }
+            
+            None
+        };
+        
+        match matched {
+            $(Some($keyword) => {
+                // SAFETY: The keyword was found in the remaining bytes.
+                unsafe { cursor.advance_n_unchecked($keyword.len()) }
+                $arm
+            })+
+            _ => $fallback,
+        }
+    }};
+}
+
+/// Checks if a keyword of `len` bytes at the start of `remaining` is not followed by a word byte that
+/// would continue it.
+#[doc(hidden)]
+#[inline]
+pub const fn __is_keyword_end(remaining: &[u8], len: usize) -> bool {
+    const fn is_word(x: u8) -> bool {
+        matches!(x, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_')
+    }
+    
+    len >= remaining.len() || !is_word(remaining[len - 1]) || !is_word(remaining[len])
+}
+
+/// Gets the length of the longest keyword. Fails to compile if a keyword is empty.
+#[doc(hidden)]
+pub const fn __max_keyword_len(keywords: &[&[u8]]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    
+    while i < keywords.len() {
+        assert!(!keywords[i].is_empty(), "keywords must not be empty");
+        
+        if keywords[i].len() > max {
+            max = keywords[i].len();
+        }
+        i += 1;
+    }
+    
+    max
+}
+
+/// Decodes the first char of `bytes` as UTF-8. Returns the char and its width in bytes.
+/// 
+/// Overlong sequences, surrogates and values greater than `U+10FFFF` are rejected, so the
+/// decoded bytes are always valid UTF-8.
+#[inline]
+pub fn decode_char(bytes: &[u8]) -> Result<(char, usize), Error> {
+    let (code, width) = decode_codepoint(bytes)?;
+    
+    if code < [0, 0, 0x80, 0x800, 0x10000][width] {
+        return Err(Error::Overlong);
+    }
+    
+    match char::from_u32(code) {
+        Some(c) => Ok((c, width)),
+        None => Err(Error::InvalidCodepoint),
+    }
+}
+
+/// Encodes `c` as UTF-8 into the start of `buf`. Returns the written bytes.
+/// 
+/// # Panics
+/// 
+/// Panics if `buf` is too short for the encoded char. A `buf` of at least 4 bytes fits every char.
+#[inline]
+pub fn encode_char_utf8(c: char, buf: &mut [u8]) -> &mut [u8] {
+    let code = c as u32;
+    
+    match code {
+        0..=0x7F => {
+            buf[0] = code as u8;
+            &mut buf[..1]
+        }
+        0x80..=0x7FF => {
+            let buf = &mut buf[..2];
+            buf[0] = 0b1100_0000 | (code >> 6) as u8;
+            buf[1] = 0b1000_0000 | (code & 0b0011_1111) as u8;
+            buf
+        }
+        0x800..=0xFFFF => {
+            let buf = &mut buf[..3];
+            buf[0] = 0b1110_0000 | (code >> 12) as u8;
+            buf[1] = 0b1000_0000 | (code >> 6 & 0b0011_1111) as u8;
+            buf[2] = 0b1000_0000 | (code & 0b0011_1111) as u8;
+            buf
+        }
+        _ => {
+            let buf = &mut buf[..4];
+            buf[0] = 0b1111_0000 | (code >> 18) as u8;
+            buf[1] = 0b1000_0000 | (code >> 12 & 0b0011_1111) as u8;
+            buf[2] = 0b1000_0000 | (code >> 6 & 0b0011_1111) as u8;
+            buf[3] = 0b1000_0000 | (code & 0b0011_1111) as u8;
+            buf
+        }
+    }
+}
+
+/// Decodes the value of the first sequence of `bytes`. Returns the value and its width in bytes.
+/// 
+/// Only the structure of the sequence is validated; the value may be overlong, a surrogate or
+/// greater than `U+10FFFF`.
+#[inline]
+fn decode_codepoint(bytes: &[u8]) -> Result<(u32, usize), Error> {
+    let first_byte = match bytes.first() {
+        Some(&x) => x,
+        None => return Err(Error::Empty),
+    };
+    
+    macro_rules! next {
+        ($n:expr,$e:expr,$i:expr) => {
+            match bytes.get($n) {
+                None => return Err($e),
+                Some(&x) if x & 0b1100_0000 != 0b1000_0000 => return Err($i),
+                Some(&x) => (x & 0b0011_1111) as u32,
+            }
+        };
+    }
+    
+    let result = match UTF8_CHAR_WIDTH[first_byte as usize] {
+        0 => return Err(Error::EncounteredContinuationByte),
+        1 => (first_byte as u32, 1),
+        2 => {
+            let second = next!(1, Error::Missing2ndOf2, Error::Invalid2ndOf2);
+            (((first_byte & 0b0001_1111) as u32) << 6 | second, 2)
+        }
+        3 => {
+            let second = next!(1, Error::Missing2ndOf3, Error::Invalid2ndOf3);
+            let third = next!(2, Error::Missing3rdOf3, Error::Invalid3rdOf3);
+            (((first_byte & 0b0000_1111) as u32) << 12 | second << 6 | third, 3)
+        }
+        4 => {
+            let second = next!(1, Error::Missing2ndOf4, Error::Invalid2ndOf4);
+            let third = next!(2, Error::Missing3rdOf4, Error::Invalid3rdOf4);
+            let fourth = next!(3, Error::Missing4thOf4, Error::Invalid4thOf4);
+            (((first_byte & 0b0000_0111) as u32) << 18 | second << 12 | third << 6 | fourth, 4)
+        }
+        _ => unsafe { unreachable_unchecked() }
+    };
+    
+    Ok(result)
+}
+
+/// The bytes [Cursor::skip_inline_whitespace] skips.
+const INLINE_WHITESPACE: [bool; 256] = {
+    let mut class = [false; 256];
+    class[b' ' as usize] = true;
+    class[b'\t' as usize] = true;
+    class[0x0B] = true;
+    class[0x0C] = true;
+    class
+};
 
 const UTF8_CHAR_WIDTH: &[u8; 256] = &[
     // 1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
@@ -1,6 +1,8 @@
 #![cfg(test)]
+#![allow(clippy::bool_assert_comparison)]
 
-use crate::bytes::Cursor;
+use crate::bytes;
+use crate::bytes::{BalanceError, Bom, CommentError, CrlfError, Cursor, DelimError, Error, EscapeError, ExpectCharError, ExpectError, JsonNumberSpan, LineEnding, MismatchError, PositionedError, TemplateError};
 
 #[test]
 fn next() {
@@ -37,6 +39,15 @@ fn peek() {
     assert_eq!(cursor.peek(), None);
 }
 
+#[test]
+fn peek_n() {
+    let cursor = Cursor::new(b"ab");
+    assert_eq!(cursor.peek_n(1), Some(b'b'));
+    assert_eq!(cursor.peek_n(2), None);
+    assert_eq!(cursor.peek_n(usize::MAX), None);
+    assert_eq!(Cursor::new(b"").peek_n(1), None);
+}
+
 #[test]
 fn has_next() {
     let mut cursor = Cursor::new("A".as_bytes());
@@ -83,4 +94,1779 @@ fn advance_char() {
     
     cursor.advance_char().unwrap();
     assert_eq!(cursor.peek(), None);
-}
\ No newline at end of file
+}
+
+#[test]
+fn consume_json_number() {
+    let mut cursor = Cursor::new("-12.5e+3,".as_bytes());
+    assert_eq!(cursor.consume_json_number(true), Some(JsonNumberSpan {
+        bytes: b"-12.5e+3",
+        has_fraction: true,
+        has_exponent: true,
+    }));
+    assert_eq!(cursor.peek(), Some(b','));
+
+    let mut cursor = Cursor::new("0]".as_bytes());
+    assert_eq!(cursor.consume_json_number(true), Some(JsonNumberSpan {
+        bytes: b"0",
+        has_fraction: false,
+        has_exponent: false,
+    }));
+    assert_eq!(cursor.peek(), Some(b']'));
+
+    let mut cursor = Cursor::new("012".as_bytes());
+    assert_eq!(cursor.consume_json_number(true), None);
+    assert_eq!(cursor.peek(), Some(b'0'));
+    assert_eq!(cursor.consume_json_number(false).map(|n| n.bytes), Some(&b"012"[..]));
+    
+    for invalid in ["-", "1.", "1.e3", "1e", "1e+", ".5", "+1"] {
+        let mut cursor = Cursor::new(invalid.as_bytes());
+        assert_eq!(cursor.consume_json_number(false), None);
+        assert_eq!(cursor.peek(), invalid.as_bytes().first().copied());
+    }
+}
+
+#[test]
+fn bytes_map() {
+    let mut cursor = Cursor::new("abc".as_bytes());
+    assert_eq!(cursor.bytes_map(|x| x.to_ascii_uppercase()).next(), Some(b'A'));
+    assert_eq!(cursor.peek(), Some(b'b'));
+    
+    assert!(cursor.bytes_map(|x| x.to_ascii_uppercase()).eq(*b"BC"));
+    assert_eq!(cursor.peek(), None);
+}
+
+#[test]
+fn match_keyword() {
+    fn keyword(cursor: &mut Cursor) -> u8 {
+        crate::match_keyword!(cursor, {
+            b"if" => 1,
+            b"in" => 2,
+            b"int" => 3,
+            _ => 0,
+        })
+    }
+    
+    let mut cursor = Cursor::new("int in if x".as_bytes());
+    assert_eq!(keyword(&mut cursor), 3);
+    cursor.skip_ascii_whitespace();
+    assert_eq!(keyword(&mut cursor), 2);
+    cursor.skip_ascii_whitespace();
+    assert_eq!(keyword(&mut cursor), 1);
+    cursor.skip_ascii_whitespace();
+    assert_eq!(keyword(&mut cursor), 0);
+    assert_eq!(cursor.peek(), Some(b'x'));
+}
+
+#[test]
+fn match_keyword_boundary() {
+    fn keyword(cursor: &mut Cursor) -> u8 {
+        crate::match_keyword!(cursor, {
+            b"if" => 1,
+            b"else" => 2,
+            b"+" => 3,
+            b"+=" => 4,
+            _ => 0,
+        })
+    }
+    
+    for input in [&b"iffy"[..], b"elsewhere", b"if_", b"else2"] {
+        let mut cursor = Cursor::new(input);
+        assert_eq!(keyword(&mut cursor), 0);
+        assert_eq!(cursor.byte_offset(), 0);
+    }
+    
+    let mut cursor = Cursor::new(b"if(else{+a+=b");
+    assert_eq!(keyword(&mut cursor), 1);
+    cursor.advance();
+    assert_eq!(keyword(&mut cursor), 2);
+    cursor.advance();
+    assert_eq!(keyword(&mut cursor), 3);
+    cursor.advance();
+    assert_eq!(keyword(&mut cursor), 4);
+    assert_eq!(cursor.peek(), Some(b'b'));
+}
+
+#[test]
+fn match_keyword_evaluates_cursor_once() {
+    let mut cursors = [Cursor::new(b"if"), Cursor::new(b"x")];
+    let mut i = 0;
+    let matched = crate::match_keyword!(cursors[{ i += 1; i - 1 }], {
+        b"if" => true,
+        _ => false,
+    });
+    assert!(matched);
+    assert_eq!(i, 1);
+    assert!(!cursors[0].has_next());
+    
+    let mut cursor = Cursor::new(b"if");
+    assert!(crate::match_keyword!(cursor, { b"if" => true, _ => false }));
+}
+
+#[test]
+fn to_line_start() {
+    let mut cursor = Cursor::new("ab\r\ncd".as_bytes());
+    cursor.to_line_start();
+    assert_eq!(cursor.peek(), Some(b'a'));
+    
+    cursor.advance();
+    cursor.to_line_start();
+    assert_eq!(cursor.peek(), Some(b'a'));
+    
+    let _ = cursor.next_lfn();
+    let _ = cursor.next_lfn();
+    let _ = cursor.next_lfn();
+    cursor.to_line_start();
+    assert_eq!(cursor.peek(), Some(b'c'));
+    
+    cursor.advance();
+    cursor.advance();
+    cursor.to_line_start();
+    assert_eq!(cursor.peek(), Some(b'c'));
+}
+
+#[test]
+fn to_line_end() {
+    let mut cursor = Cursor::new("ab\r\ncd".as_bytes());
+    cursor.to_line_end();
+    assert_eq!(cursor.peek(), Some(b'\r'));
+    
+    cursor.to_line_end();
+    assert_eq!(cursor.peek(), Some(b'\r'));
+    
+    let _ = cursor.next_lfn();
+    cursor.to_line_end();
+    assert_eq!(cursor.peek(), None);
+}
+
+const DELIMITERS: [bool; 256] = {
+    let mut class = [false; 256];
+    class[b' ' as usize] = true;
+    class[b',' as usize] = true;
+    class
+};
+
+#[test]
+fn consume_class() {
+    let mut cursor = Cursor::new(", ,a".as_bytes());
+    assert_eq!(cursor.consume_class(&DELIMITERS), 3);
+    assert_eq!(cursor.peek(), Some(b'a'));
+    assert_eq!(cursor.consume_class(&DELIMITERS), 0);
+}
+
+#[test]
+fn consume_until_class() {
+    let mut cursor = Cursor::new("ab,c".as_bytes());
+    assert_eq!(cursor.consume_until_class(&DELIMITERS), 2);
+    assert_eq!(cursor.peek(), Some(b','));
+    assert_eq!(cursor.consume_until_class(&DELIMITERS), 0);
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_until_class(&DELIMITERS), 1);
+    assert_eq!(cursor.peek(), None);
+}
+
+#[test]
+fn index() {
+    let mut cursor = Cursor::new("A\r\nB".as_bytes());
+    assert_eq!(cursor.index(), 0);
+    
+    let _ = cursor.next_lfn();
+    let _ = cursor.next_lfn();
+    assert_eq!(cursor.index(), 3);
+}
+
+#[test]
+fn consumed_since() {
+    let mut cursor = Cursor::new("ABC".as_bytes());
+    cursor.advance();
+    
+    let start = cursor.index();
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.consumed_since(start), 2);
+    assert_eq!(cursor.consumed_since(cursor.index()), 0);
+}
+
+#[test]
+fn decode_char() {
+    assert_eq!(bytes::decode_char(b"Ab"), Ok(('A', 1)));
+    assert_eq!(bytes::decode_char("ä".as_bytes()), Ok(('ä', 2)));
+    assert_eq!(bytes::decode_char("€".as_bytes()), Ok(('€', 3)));
+    assert_eq!(bytes::decode_char("🦀".as_bytes()), Ok(('🦀', 4)));
+    
+    assert_eq!(bytes::decode_char(b""), Err(Error::Empty));
+    assert_eq!(bytes::decode_char(b"\x80"), Err(Error::EncounteredContinuationByte));
+    assert_eq!(bytes::decode_char(b"\xC3"), Err(Error::Missing2ndOf2));
+    assert_eq!(bytes::decode_char(b"\xE2\x82A"), Err(Error::Invalid3rdOf3));
+    assert_eq!(bytes::decode_char(b"\xED\xA0\x80"), Err(Error::InvalidCodepoint));
+    assert_eq!(bytes::decode_char(b"\xF4\x90\x80\x80"), Err(Error::InvalidCodepoint));
+    assert_eq!(bytes::decode_char(b"\xE0\x80\xAF"), Err(Error::Overlong));
+    assert_eq!(bytes::decode_char(b"\xF0\x82\x82\xAC"), Err(Error::Overlong));
+}
+
+#[test]
+fn next_char() {
+    let mut cursor = Cursor::new(b"a\xE2\x82\xAC\xC3");
+    assert_eq!(cursor.next_char(), Ok(Some('a')));
+    assert_eq!(cursor.next_char(), Ok(Some('€')));
+    assert_eq!(cursor.next_char(), Err(Error::Missing2ndOf2));
+    assert_eq!(cursor.peek(), Some(0xC3));
+    
+    let mut cursor = Cursor::new("".as_bytes());
+    assert_eq!(cursor.next_char(), Ok(None));
+}
+
+#[test]
+fn begin_recording() {
+    let mut cursor = Cursor::new("ABC".as_bytes());
+    cursor.advance();
+    
+    let mut recorder = cursor.begin_recording();
+    recorder.advance();
+    assert_eq!(recorder.stop(), b"B");
+    assert_eq!(cursor.peek(), Some(b'C'));
+}
+
+#[test]
+fn scan() {
+    let mut cursor = Cursor::new("12.5]".as_bytes());
+    let (number, text) = cursor.scan(|cursor| cursor.consume_json_number(true)).unwrap();
+    assert!(number.has_fraction);
+    assert_eq!(text, "12.5");
+    assert_eq!(cursor.peek(), Some(b']'));
+    
+    assert_eq!(cursor.scan(|cursor| {
+        cursor.advance();
+        None::<()>
+    }), None);
+    assert_eq!(cursor.peek(), None);
+    
+    let mut cursor = Cursor::new(b"a\xFF");
+    assert_eq!(cursor.scan(|cursor| cursor.next().and(cursor.next())), None);
+    assert_eq!(cursor.peek(), Some(b'a'));
+}
+
+#[test]
+fn peek_char() {
+    let cursor = Cursor::new("€".as_bytes());
+    assert_eq!(cursor.peek_char(), Ok(Some('€')));
+    assert_eq!(cursor.peek(), Some(0xE2));
+    
+    assert_eq!(Cursor::new(b"").peek_char(), Ok(None));
+    assert_eq!(Cursor::new(b"\xE2\x82").peek_char(), Err(Error::Missing3rdOf3));
+}
+
+#[test]
+fn peek_char_partial() {
+    assert_eq!(Cursor::new("€".as_bytes()).peek_char_partial(), Ok(Some('€')));
+    assert_eq!(Cursor::new(b"\xE2\x82").peek_char_partial(), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"\xF0").peek_char_partial(), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"\xE2\x82A").peek_char_partial(), Err(Error::Invalid3rdOf3));
+}
+
+#[test]
+fn count_while() {
+    let cursor = Cursor::new("    x ".as_bytes());
+    assert_eq!(cursor.count_while(|x| x == b' '), 4);
+    assert_eq!(cursor.count_while(|x| x == b'x'), 0);
+    assert_eq!(cursor.peek(), Some(b' '));
+}
+
+#[test]
+fn next_lfn_trailing_cr() {
+    let mut cursor = Cursor::new("A\r".as_bytes());
+    assert_eq!(cursor.next_lfn(), Some(b'A'));
+    assert_eq!(cursor.next_lfn(), Some(b'\n'));
+    assert!(!cursor.has_next());
+    assert_eq!(cursor.next_lfn(), None);
+}
+
+#[test]
+fn next_lfn_cr_before_other_byte() {
+    let mut cursor = Cursor::new("\rA\r\r\n".as_bytes());
+    assert_eq!(cursor.next_lfn(), Some(b'\n'));
+    assert_eq!(cursor.peek(), Some(b'A'));
+    assert_eq!(cursor.next_lfn(), Some(b'A'));
+    assert_eq!(cursor.next_lfn(), Some(b'\n'));
+    assert_eq!(cursor.peek(), Some(b'\r'));
+    assert_eq!(cursor.next_lfn(), Some(b'\n'));
+    assert_eq!(cursor.next_lfn(), None);
+}
+
+#[test]
+fn peek_chars() {
+    let cursor = Cursor::new("l€t!".as_bytes());
+    assert_eq!(cursor.peek_chars(3), Ok("l€t"));
+    assert_eq!(cursor.peek_chars(0), Ok(""));
+    assert_eq!(cursor.peek_chars(5), Err(Error::Incomplete));
+    assert_eq!(cursor.peek(), Some(b'l'));
+    
+    assert_eq!(Cursor::new(b"a\xE2\x82").peek_chars(2), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"a\x80").peek_chars(2), Err(Error::EncounteredContinuationByte));
+}
+
+#[test]
+fn peek_back() {
+    let mut cursor = Cursor::new("AB".as_bytes());
+    assert_eq!(cursor.peek_back(), None);
+    
+    cursor.advance();
+    assert_eq!(cursor.peek_back(), Some(b'A'));
+    assert_eq!(cursor.peek(), Some(b'B'));
+}
+
+#[test]
+fn at_word_boundary() {
+    let mut cursor = Cursor::new("a_1 +".as_bytes());
+    let expected = [true, false, false, true, false, false];
+    
+    for boundary in expected {
+        assert_eq!(cursor.at_word_boundary(), boundary);
+        cursor.advance();
+    }
+    
+    assert!(!Cursor::new(b"").at_word_boundary());
+}
+
+#[test]
+fn consume_while_escaped() {
+    let mut cursor = Cursor::new(r#"a\"b\\"c"#.as_bytes());
+    assert_eq!(cursor.consume_while_escaped(b'\\', |x| x != b'"'), 6);
+    assert_eq!(cursor.peek(), Some(b'"'));
+    
+    let mut cursor = Cursor::new(r"a\".as_bytes());
+    assert_eq!(cursor.consume_while_escaped(b'\\', |x| x != b'"'), 2);
+    assert_eq!(cursor.peek(), None);
+}
+
+#[test]
+fn recorder_rewind() {
+    let mut cursor = Cursor::new("ABC".as_bytes());
+    cursor.advance();
+    
+    let mut recorder = cursor.begin_recording();
+    recorder.advance();
+    recorder.advance();
+    recorder.rewind();
+    assert_eq!(cursor.index(), 1);
+    assert_eq!(cursor.peek(), Some(b'B'));
+}
+
+#[test]
+fn as_str() {
+    let mut cursor = Cursor::new("a€".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.as_slice(), "a€".as_bytes());
+    assert_eq!(cursor.as_str(), Ok("a€"));
+    assert_eq!(unsafe { cursor.as_str_unchecked() }, "a€");
+    
+    assert!(Cursor::new(b"a\xFF").as_str().is_err());
+}
+
+#[test]
+fn consumed() {
+    let mut cursor = Cursor::new("AB".as_bytes());
+    assert_eq!(cursor.consumed(), b"");
+    
+    cursor.advance();
+    assert_eq!(cursor.consumed(), b"A");
+    assert_eq!(cursor.remaining(), b"B");
+}
+
+#[test]
+fn line_column() {
+    let mut cursor = Cursor::new("a€\r\n\tb\rc\n".as_bytes());
+    assert_eq!(cursor.line_column(), (1, 1));
+    
+    cursor.advance();
+    cursor.advance_char().unwrap();
+    assert_eq!(cursor.line_column(), (1, 3));
+    
+    let _ = cursor.next_lfn();
+    assert_eq!(cursor.line_column(), (2, 1));
+    
+    cursor.advance();
+    assert_eq!(cursor.line_column(), (2, 2));
+    
+    cursor.set_tab_width(4);
+    assert_eq!(cursor.line_column(), (2, 5));
+    
+    cursor.advance();
+    assert_eq!(cursor.line_column(), (2, 6));
+    
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.line_column(), (3, 2));
+    
+    cursor.advance();
+    assert_eq!(cursor.line_column(), (4, 1));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dump() {
+    let mut cursor = Cursor::new("ab\ncd".as_bytes());
+    cursor.advance();
+    cursor.advance();
+    
+    let dump = cursor.dump();
+    let mut lines = dump.lines().skip(1);
+    assert_eq!(lines.next(), Some("|ab\\ncd|"));
+    assert_eq!(lines.next(), Some("   ^"));
+    assert_eq!(lines.next(), Some("61 62 | 0a 63 64"));
+    assert_eq!(lines.next(), None);
+    assert!(dump.contains("(2)"));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn dump_window() {
+    let input = [b'a'; 100];
+    let mut cursor = Cursor::new_with_base(&input, 1000);
+    for _ in 0..40 {
+        cursor.advance();
+    }
+    
+    let dump = cursor.dump();
+    let mut lines = dump.lines();
+    let header = lines.next().unwrap();
+    assert!(header.contains("(40), end: "));
+    assert!(header.contains("(100), index: 1040"));
+    assert_eq!(lines.next().map(str::len), Some(3 + 64 + 3));
+    assert_eq!(lines.next(), Some("                                   ^"));
+    
+    let dump = Cursor::new(&input).dump();
+    assert!(dump.lines().nth(1).unwrap().starts_with("|a"));
+    assert!(dump.lines().nth(1).unwrap().ends_with("a..."));
+    assert_eq!(dump.lines().nth(2), Some(" ^"));
+}
+
+#[test]
+fn consume_run() {
+    let mut cursor = Cursor::new("   xx".as_bytes());
+    assert_eq!(cursor.consume_run(), Some((b' ', 3)));
+    assert_eq!(cursor.consume_run(), Some((b'x', 2)));
+    assert_eq!(cursor.consume_run(), None);
+}
+
+#[test]
+fn error_u8() {
+    for (i, error) in Error::ALL.into_iter().enumerate() {
+        assert_eq!(error.as_u8() as usize, i);
+        assert_eq!(Error::from_u8(error.as_u8()), Some(error));
+    }
+    
+    assert_eq!(Error::from_u8(Error::ALL.len() as u8), None);
+    assert_eq!(Error::from_u8(u8::MAX), None);
+}
+
+#[test]
+fn consume_line_bytes() {
+    let mut cursor = Cursor::new(b"a\xFF\r\n\nb\rc\n");
+    assert_eq!(cursor.consume_line_bytes(), Some(&b"a\xFF"[..]));
+    assert_eq!(cursor.consume_line_bytes(), Some(&b""[..]));
+    assert_eq!(cursor.consume_line_bytes(), Some(&b"b"[..]));
+    assert_eq!(cursor.consume_line_bytes(), Some(&b"c"[..]));
+    assert_eq!(cursor.consume_line_bytes(), None);
+}
+
+#[test]
+fn consume_uint_radix() {
+    let mut cursor = Cursor::new("ff_1012".as_bytes());
+    assert_eq!(cursor.consume_uint_radix(16), Some(255));
+    assert_eq!(cursor.consume_uint_radix(16), None);
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_uint_radix(2), Some(5));
+    assert_eq!(cursor.peek(), Some(b'2'));
+    
+    let mut cursor = Cursor::new("18446744073709551615 18446744073709551616".as_bytes());
+    assert_eq!(cursor.consume_uint_radix(10), Some(u64::MAX));
+    cursor.advance();
+    assert_eq!(cursor.consume_uint_radix(10), None);
+    assert_eq!(cursor.peek(), Some(b'1'));
+    
+    assert_eq!(Cursor::new(b"Zz").consume_uint_radix(36), Some(35 * 36 + 35));
+}
+
+#[test]
+fn remaining_len() {
+    let mut cursor = Cursor::new("a€".as_bytes());
+    assert_eq!(cursor.remaining_len(), 4);
+    
+    cursor.advance();
+    assert_eq!(cursor.remaining_len(), 3);
+    assert_eq!(cursor.estimate_chars_remaining(), 3);
+}
+
+#[test]
+fn peek_char_bytes() {
+    let cursor = Cursor::new("€a".as_bytes());
+    assert_eq!(cursor.peek_char_bytes(), Ok(Some("€".as_bytes())));
+    assert_eq!(cursor.peek(), Some(0xE2));
+    
+    assert_eq!(Cursor::new(b"").peek_char_bytes(), Ok(None));
+    assert_eq!(Cursor::new(b"\xE2\x82").peek_char_bytes(), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"\xE2A").peek_char_bytes(), Err(Error::Invalid2ndOf3));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "rewound past the first byte")]
+fn rewind_unchecked_at_start() {
+    let mut cursor = Cursor::new("A".as_bytes());
+    unsafe { cursor.rewind_unchecked() }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn line_starts() {
+    let mut cursor = Cursor::new("ab\r\nc\rd\n".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.line_starts(), [0, 4, 6, 8]);
+    
+    assert_eq!(Cursor::new(b"").line_starts(), [0]);
+    assert_eq!(Cursor::new(b"a").line_starts(), [0]);
+}
+
+#[test]
+fn take_balanced() {
+    let mut cursor = Cursor::new("(a(b)c)d".as_bytes());
+    assert_eq!(cursor.take_balanced(b'(', b')'), Ok(&b"a(b)c"[..]));
+    assert_eq!(cursor.peek(), Some(b'd'));
+    assert_eq!(cursor.take_balanced(b'(', b')'), Err(BalanceError::MissingOpen));
+    
+    let mut cursor = Cursor::new("x(()".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.take_balanced(b'(', b')'), Err(BalanceError::Unbalanced(1)));
+    assert_eq!(cursor.index(), 1);
+    
+    assert_eq!(Cursor::new(b"()").take_balanced(b'(', b')'), Ok(&b""[..]));
+    assert_eq!(Cursor::new(b"|a|b|").take_balanced(b'|', b'|'), Ok(&b"a"[..]));
+}
+
+#[test]
+fn reset_index() {
+    let mut cursor = Cursor::new("ABCD".as_bytes());
+    cursor.advance();
+    cursor.advance();
+    
+    cursor.reset_index(0);
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.byte_offset(), 2);
+    
+    cursor.advance();
+    assert_eq!(cursor.index(), 1);
+    
+    cursor.rewind();
+    assert_eq!(cursor.index(), 0);
+    
+    cursor.reset_index(100);
+    cursor.rewind();
+    assert_eq!(cursor.index(), 99);
+    assert_eq!(cursor.byte_offset(), 1);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "index underflow")]
+fn index_underflow() {
+    let mut cursor = Cursor::new("AB".as_bytes());
+    cursor.advance();
+    cursor.reset_index(0);
+    cursor.rewind();
+    cursor.index();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "index overflow")]
+fn index_overflow() {
+    let mut cursor = Cursor::new("AB".as_bytes());
+    cursor.reset_index(u64::MAX);
+    cursor.advance();
+    cursor.index();
+}
+
+#[test]
+fn advance_char_or_byte() {
+    let mut cursor = Cursor::new(b"a\xE2\x82\xAC\x80\xF0\x9F");
+    assert_eq!(cursor.advance_char_or_byte(), 1);
+    assert_eq!(cursor.advance_char_or_byte(), 3);
+    assert_eq!(cursor.advance_char_or_byte(), 1);
+    assert_eq!(cursor.advance_char_or_byte(), 2);
+    assert_eq!(cursor.advance_char_or_byte(), 0);
+}
+
+#[test]
+fn iter_consumed_rev() {
+    let mut cursor = Cursor::new("ab\\  c".as_bytes());
+    for _ in 0..5 {
+        cursor.advance();
+    }
+    assert!(cursor.iter_consumed_rev().eq(*b"  \\ba"));
+    assert_eq!(cursor.iter_consumed_rev().find(|&x| x != b' '), Some(b'\\'));
+    assert_eq!(cursor.peek(), Some(b'c'));
+}
+
+#[test]
+fn rewind_char() {
+    let mut cursor = Cursor::new("a€🦀".as_bytes());
+    while cursor.has_next() {
+        cursor.advance();
+    }
+    
+    assert_eq!(cursor.rewind_char(), Some('🦀'));
+    assert_eq!(cursor.rewind_char(), Some('€'));
+    assert_eq!(cursor.rewind_char(), Some('a'));
+    assert_eq!(cursor.rewind_char(), None);
+    
+    let mut cursor = Cursor::new(b"a\x82\xAC");
+    cursor.advance();
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.rewind_char(), None);
+    assert_eq!(cursor.index(), 3);
+}
+
+#[test]
+fn consume_slice() {
+    let mut cursor = Cursor::new("let x".as_bytes());
+    assert!(!cursor.consume_slice(b"lex"));
+    assert!(cursor.consume_slice(b"let"));
+    assert_eq!(cursor.peek(), Some(b' '));
+    assert!(!cursor.consume_slice(b" xy"));
+}
+
+#[test]
+fn expect_slice() {
+    let mut cursor = Cursor::new(b"\x89PNG\x0D\x0A\x1A\x0A");
+    assert_eq!(cursor.expect_slice(b"\x89PX"), Err(MismatchError { offset: 2, found: Some(b'N') }));
+    assert_eq!(cursor.index(), 0);
+    
+    assert_eq!(cursor.expect_slice(b"\x89PNG"), Ok(()));
+    assert_eq!(cursor.expect_slice(b"\x0D\x0A\x1A\x0A\x00"), Err(MismatchError { offset: 4, found: None }));
+    assert_eq!(cursor.index(), 4);
+}
+
+#[test]
+fn next_line_ending() {
+    let mut cursor = Cursor::new("a\r\nb\nc\r".as_bytes());
+    assert_eq!(cursor.next_line_ending(LineEnding::CrLf), Ok(Some(b'a')));
+    assert_eq!(cursor.next_line_ending(LineEnding::CrLf), Ok(Some(b'\n')));
+    assert_eq!(cursor.next_line_ending(LineEnding::CrLf), Ok(Some(b'b')));
+    assert_eq!(cursor.next_line_ending(LineEnding::CrLf), Err(LineEnding::Lf));
+    assert_eq!(cursor.peek(), Some(b'\n'));
+    assert_eq!(cursor.next_line_ending(LineEnding::Lf), Ok(Some(b'\n')));
+    assert_eq!(cursor.next_line_ending(LineEnding::Lf), Ok(Some(b'c')));
+    assert_eq!(cursor.next_line_ending(LineEnding::Lf), Err(LineEnding::Cr));
+    assert_eq!(cursor.next_line_ending(LineEnding::Any), Ok(Some(b'\n')));
+    assert_eq!(cursor.next_line_ending(LineEnding::Any), Ok(None));
+}
+
+#[test]
+fn consume_codepoint() {
+    let mut cursor = Cursor::new(b"a\xE2\x82\xAC\xED\xA0\x80\xE0\x80\xAF\xC3");
+    assert_eq!(cursor.consume_codepoint(), Ok(Some(0x61)));
+    assert_eq!(cursor.consume_codepoint(), Ok(Some(0x20AC)));
+    assert_eq!(cursor.consume_codepoint(), Ok(Some(0xD800)));
+    assert_eq!(cursor.consume_codepoint(), Ok(Some(0x2F)));
+    assert_eq!(cursor.consume_codepoint(), Err(Error::Missing2ndOf2));
+    assert_eq!(cursor.remaining(), b"\xC3");
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_codepoint(), Ok(None));
+}
+
+#[test]
+fn remaining_eq() {
+    let mut cursor = Cursor::new("GET".as_bytes());
+    assert!(cursor.remaining_eq(b"GET"));
+    assert!(!cursor.remaining_eq(b"GE"));
+    
+    cursor.advance();
+    assert!(cursor.remaining_eq(b"ET"));
+    assert!(!cursor.remaining_eq(b"ETX"));
+}
+
+#[test]
+fn encode_char_utf8() {
+    let mut buf = [0; 4];
+    
+    for c in ['A', 'ä', '€', '🦀', '\u{7FF}', '\u{FFFF}', char::MAX] {
+        let mut expected = [0; 4];
+        assert_eq!(bytes::encode_char_utf8(c, &mut buf), c.encode_utf8(&mut expected).as_bytes());
+        assert_eq!(bytes::decode_char(&buf), Ok((c, c.len_utf8())));
+    }
+}
+
+#[test]
+#[should_panic]
+fn encode_char_utf8_short_buffer() {
+    bytes::encode_char_utf8('€', &mut [0; 2]);
+}
+
+#[test]
+fn skip_shebang() {
+    let mut cursor = Cursor::new("#!/bin/sh\r\n#!x".as_bytes());
+    assert!(cursor.skip_shebang());
+    assert_eq!(cursor.peek(), Some(b'#'));
+    assert!(!cursor.skip_shebang());
+    assert_eq!(cursor.peek(), Some(b'#'));
+    
+    let mut cursor = Cursor::new("#x".as_bytes());
+    assert!(!cursor.skip_shebang());
+    assert_eq!(cursor.index(), 0);
+    
+    let mut cursor = Cursor::new("#!".as_bytes());
+    assert!(cursor.skip_shebang());
+    assert!(!cursor.has_next());
+}
+
+#[test]
+fn run_length() {
+    let cursor = Cursor::new("   x".as_bytes());
+    assert_eq!(cursor.run_length(b' '), 3);
+    assert_eq!(cursor.run_length(b'x'), 0);
+    assert_eq!(cursor.index(), 0);
+}
+
+#[test]
+fn detect_bom() {
+    assert_eq!(Cursor::new(b"\xEF\xBB\xBFa").detect_bom(), Some(Bom::Utf8));
+    assert_eq!(Cursor::new(b"\xFF\xFEa\x00").detect_bom(), Some(Bom::Utf16Le));
+    assert_eq!(Cursor::new(b"\xFE\xFF\x00a").detect_bom(), Some(Bom::Utf16Be));
+    assert_eq!(Cursor::new(b"\xFF\xFE\x00\x00").detect_bom(), Some(Bom::Utf32Le));
+    assert_eq!(Cursor::new(b"\x00\x00\xFE\xFF").detect_bom(), Some(Bom::Utf32Be));
+    assert_eq!(Cursor::new(b"\xEF\xBB").detect_bom(), None);
+    
+    let mut cursor = Cursor::new(b"a\xEF\xBB\xBF");
+    cursor.advance();
+    assert_eq!(cursor.detect_bom(), None);
+}
+
+#[test]
+fn skip_detected_bom() {
+    let mut cursor = Cursor::new(b"\xFF\xFE\x00\x00a");
+    assert_eq!(cursor.skip_detected_bom(), Some(Bom::Utf32Le));
+    assert_eq!(cursor.peek(), Some(b'a'));
+    assert_eq!(cursor.skip_detected_bom(), None);
+    assert_eq!(cursor.peek(), Some(b'a'));
+}
+
+#[test]
+fn slice_between() {
+    let mut cursor = Cursor::new("abcd".as_bytes());
+    assert_eq!(cursor.slice_between(1, 3), Some(&b"bc"[..]));
+    assert_eq!(cursor.slice_between(4, 4), Some(&b""[..]));
+    assert_eq!(cursor.slice_between(3, 1), None);
+    assert_eq!(cursor.slice_between(2, 5), None);
+    assert_eq!(cursor.slice_between(u64::MAX, 2), None);
+    
+    cursor.advance();
+    cursor.reset_index(10);
+    assert_eq!(cursor.slice_between(9, 11), Some(&b"ab"[..]));
+    assert_eq!(cursor.slice_between(8, 11), None);
+}
+
+#[test]
+fn advance_char_strict() {
+    let mut cursor = Cursor::new("a\r\n€".as_bytes());
+    assert_eq!(cursor.advance_char_strict(), Ok(()));
+    assert_eq!(cursor.advance_char_strict(), Ok(()));
+    assert_eq!(cursor.peek(), Some(0xE2));
+    assert_eq!(cursor.advance_char_strict(), Ok(()));
+    assert_eq!(cursor.advance_char_strict(), Ok(()));
+    assert!(!cursor.has_next());
+    
+    for (input, error) in [
+        (&b"\xC0\xAF"[..], Error::EncounteredContinuationByte),
+        (b"\xE0\x80\xAF", Error::Overlong),
+        (b"\xED\xA0\x80", Error::InvalidCodepoint),
+        (b"\xF4\x90\x80\x80", Error::InvalidCodepoint),
+        (b"\xE2\x82", Error::Missing3rdOf3),
+    ] {
+        let mut cursor = Cursor::new(input);
+        assert_eq!(cursor.advance_char_strict(), Err(error));
+        assert_eq!(cursor.index(), 0);
+    }
+    
+    assert_eq!(Cursor::new(b"\xE0\x80\xAF").advance_char(), Ok(()));
+    
+    for lead in [0xC0, 0xC1, 0xF5, 0xF8, 0xFF] {
+        let input = [lead, 0xAF, 0x80, 0x80];
+        assert_eq!(Cursor::new(&input).advance_char(), Err(Error::EncounteredContinuationByte));
+        assert_eq!(Cursor::new(&input).advance_char_strict(), Err(Error::EncounteredContinuationByte));
+    }
+}
+
+#[test]
+fn skip_whitespace_tracking_newlines() {
+    let mut cursor = Cursor::new(" \t\r\n\n\r x".as_bytes());
+    assert_eq!(cursor.skip_whitespace_tracking_newlines(), (7, 3));
+    assert_eq!(cursor.peek(), Some(b'x'));
+    assert_eq!(cursor.skip_whitespace_tracking_newlines(), (0, 0));
+}
+
+#[test]
+fn into_remaining() {
+    let mut cursor = Cursor::new("ab\ncd".as_bytes());
+    cursor.set_tab_width(4);
+    let _ = cursor.consume_line_bytes();
+    
+    let mut cursor = cursor.into_remaining();
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.as_slice(), b"cd");
+    assert!(!cursor.can_rewind());
+    
+    cursor.advance();
+    assert_eq!(cursor.index(), 1);
+    assert_eq!(cursor.line_column(), (1, 2));
+}
+
+#[test]
+fn consume_c_identifier() {
+    let mut cursor = Cursor::new("_a1 b 1c".as_bytes());
+    assert_eq!(cursor.consume_c_identifier(), Some("_a1"));
+    assert_eq!(cursor.consume_c_identifier(), None);
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_c_identifier(), Some("b"));
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_c_identifier(), None);
+    assert_eq!(cursor.peek(), Some(b'1'));
+}
+
+#[test]
+fn count_chars() {
+    let mut cursor = Cursor::new("xa€🦀b".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.count_chars(), Ok(4));
+    assert_eq!(cursor.index(), 1);
+    
+    let mut cursor = Cursor::new(b"xab\xE2\x82");
+    cursor.advance();
+    assert_eq!(cursor.count_chars(), Err((3, Error::Missing3rdOf3)));
+    assert_eq!(Cursor::new(b"").count_chars(), Ok(0));
+}
+
+#[test]
+fn peek_matches() {
+    let cursor = Cursor::new("abc".as_bytes());
+    assert_eq!(cursor.peek_matches(|x| x.iter().position(|&x| x == b'c')), Some(2));
+    assert_eq!(cursor.peek_matches(|_| None), None);
+    assert_eq!(cursor.peek_matches(|_| Some(4)), None);
+    assert_eq!(cursor.index(), 0);
+}
+
+#[test]
+fn consume_matches() {
+    let mut cursor = Cursor::new("abc".as_bytes());
+    assert_eq!(cursor.consume_matches(|x| x.iter().position(|&x| x == b'c')), Some(2));
+    assert_eq!(cursor.peek(), Some(b'c'));
+    assert_eq!(cursor.consume_matches(|_| Some(2)), None);
+    assert_eq!(cursor.peek(), Some(b'c'));
+}
+
+#[test]
+fn recorder_rewind_crlf() {
+    let mut cursor = Cursor::new("a\r\nb".as_bytes());
+    
+    let mut recorder = cursor.begin_recording();
+    assert_eq!(recorder.next_lfn(), Some(b'a'));
+    assert_eq!(recorder.next_lfn(), Some(b'\n'));
+    assert_eq!(recorder.next_lfn(), Some(b'b'));
+    assert_eq!(recorder.index(), 4);
+    recorder.rewind();
+    
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.byte_offset(), 0);
+    assert_eq!(cursor.remaining(), b"a\r\nb");
+}
+
+#[test]
+fn peek_ascii() {
+    let mut cursor = Cursor::new("a€".as_bytes());
+    assert_eq!(cursor.peek_ascii(), Some(b'a'));
+    
+    cursor.advance();
+    assert_eq!(cursor.peek_ascii(), None);
+    assert!(cursor.has_next());
+    
+    assert_eq!(Cursor::new(b"").peek_ascii(), None);
+}
+
+#[test]
+fn set_end() {
+    let mut cursor = Cursor::new("\x03abcd".as_bytes());
+    let len = cursor.next().unwrap() as usize;
+    
+    let prev = cursor.set_end(len).unwrap();
+    assert_eq!(cursor.remaining(), b"abc");
+    assert!(cursor.set_end(4).is_none());
+    
+    while cursor.has_next() {
+        cursor.advance();
+    }
+    
+    cursor.restore_end(prev);
+    assert_eq!(cursor.remaining(), b"d");
+}
+
+#[test]
+#[should_panic(expected = "the end does not belong to this cursor")]
+fn restore_end_foreign() {
+    let mut long = Cursor::new("abcd".as_bytes());
+    let mut short = Cursor::new(&long.as_slice()[..2]);
+    let prev = long.set_end(3).unwrap();
+    
+    short.restore_end(prev);
+}
+
+#[test]
+fn consume_newline() {
+    let mut cursor = Cursor::new("\r\n\nx\r".as_bytes());
+    assert!(cursor.consume_newline());
+    assert_eq!(cursor.index(), 2);
+    assert!(cursor.consume_newline());
+    assert!(!cursor.consume_newline());
+    assert_eq!(cursor.peek(), Some(b'x'));
+    
+    cursor.advance();
+    assert!(cursor.consume_newline());
+    assert!(!cursor.consume_newline());
+}
+
+#[test]
+fn read_array() {
+    let mut cursor = Cursor::new(b"\x7FELF\x02");
+    assert_eq!(cursor.read_array(), Some(*b"\x7FELF"));
+    assert_eq!(cursor.read_array::<2>(), None);
+    assert_eq!(cursor.read_array(), Some([2]));
+    assert_eq!(cursor.read_array(), Some([]));
+}
+
+#[test]
+fn consume_f64() {
+    let mut cursor = Cursor::new("-1.5e3,+2,3.x,4e+,.5".as_bytes());
+    assert_eq!(cursor.consume_f64(), Some(-1500.0));
+    assert_eq!(cursor.next(), Some(b','));
+    assert_eq!(cursor.consume_f64(), Some(2.0));
+    assert_eq!(cursor.next(), Some(b','));
+    assert_eq!(cursor.consume_f64(), Some(3.0));
+    assert_eq!(cursor.next(), Some(b'.'));
+    assert_eq!(cursor.next(), Some(b'x'));
+    assert_eq!(cursor.next(), Some(b','));
+    assert_eq!(cursor.consume_f64(), Some(4.0));
+    assert_eq!(cursor.peek(), Some(b'e'));
+    cursor.consume_slice(b"e+,");
+    assert_eq!(cursor.consume_f64(), None);
+    assert_eq!(cursor.peek(), Some(b'.'));
+    
+    assert_eq!(Cursor::new(b"0.1").consume_f64(), Some(0.1));
+    assert_eq!(Cursor::new(b"-").consume_f64(), None);
+}
+
+#[test]
+fn skip_line_comment() {
+    let mut cursor = Cursor::new("// a\r\nb # c".as_bytes());
+    assert!(cursor.skip_line_comment(b"//"));
+    assert_eq!(cursor.peek(), Some(b'\r'));
+    assert!(!cursor.skip_line_comment(b"//"));
+    
+    cursor.consume_slice(b"\r\nb ");
+    assert!(cursor.skip_line_comment(b"#"));
+    assert!(!cursor.has_next());
+}
+
+#[test]
+fn skip_block_comment() {
+    let mut cursor = Cursor::new("/* a /* b */ c */x".as_bytes());
+    assert_eq!(cursor.skip_block_comment(b"/*", b"*/", true), Ok(true));
+    assert_eq!(cursor.peek(), Some(b'x'));
+    assert_eq!(cursor.skip_block_comment(b"/*", b"*/", true), Ok(false));
+    
+    let mut cursor = Cursor::new("/* a /* b */ c */x".as_bytes());
+    assert_eq!(cursor.skip_block_comment(b"/*", b"*/", false), Ok(true));
+    assert_eq!(cursor.peek(), Some(b' '));
+    
+    let mut cursor = Cursor::new("x/* a /* */".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.skip_block_comment(b"/*", b"*/", true), Err(CommentError::Unterminated(1)));
+    assert_eq!(cursor.index(), 1);
+    assert_eq!(cursor.skip_block_comment(b"/*", b"*/", false), Ok(true));
+}
+
+#[test]
+#[should_panic(expected = "comment delimiters must not be empty")]
+fn skip_block_comment_empty_open() {
+    let _ = Cursor::new(b"abc").skip_block_comment(b"", b"*/", true);
+}
+
+#[test]
+fn peek_eq() {
+    let cursor = Cursor::new("{".as_bytes());
+    assert!(cursor.peek_eq(b'{'));
+    assert!(!cursor.peek_eq(b'}'));
+    assert!(!Cursor::new(b"").peek_eq(b'{'));
+}
+
+#[test]
+fn peek_in() {
+    let cursor = Cursor::new("+".as_bytes());
+    assert!(cursor.peek_in(b"+-"));
+    assert!(!cursor.peek_in(b"*/"));
+    assert!(!cursor.peek_in(b""));
+    assert!(!Cursor::new(b"").peek_in(b"+-"));
+}
+
+#[test]
+fn consume_rest() {
+    let mut cursor = Cursor::new("ab:data".as_bytes());
+    cursor.consume_slice(b"ab:");
+    assert_eq!(cursor.consume_rest(), b"data");
+    assert_eq!(cursor.index(), 7);
+    assert_eq!(cursor.consume_rest(), b"");
+}
+
+#[test]
+fn checkpoint() {
+    let mut cursor = Cursor::new("abc".as_bytes());
+    let before = cursor.checkpoint();
+    cursor.advance();
+    cursor.advance();
+    let after = cursor.checkpoint();
+    
+    assert!(after > before);
+    assert_eq!(before.distance(&after), 2);
+    assert_eq!(after.distance(&before), -2);
+    assert_eq!(after.distance(&after), 0);
+    
+    cursor.restore(before);
+    assert_eq!(cursor.index(), 0);
+    cursor.restore(after);
+    assert_eq!(cursor.peek(), Some(b'c'));
+}
+
+#[test]
+#[should_panic(expected = "the checkpoint is outside the input")]
+fn restore_foreign() {
+    let long = Cursor::new("abc".as_bytes());
+    let mut end = Cursor::new(&long.as_slice()[3..]);
+    end.restore(long.checkpoint());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn repeat() {
+    let mut cursor = Cursor::new("a,b,c;".as_bytes());
+    let items = cursor.repeat(|cursor| {
+        let item = cursor.consume_c_identifier()?;
+        cursor.consume_slice(b",");
+        Some(item)
+    });
+    assert_eq!(items, ["a", "b", "c"]);
+    assert_eq!(cursor.peek(), Some(b';'));
+}
+
+#[test]
+#[cfg(all(feature = "alloc", debug_assertions))]
+#[should_panic(expected = "repeat made no progress")]
+fn repeat_no_progress() {
+    Cursor::new("a".as_bytes()).repeat(|cursor| cursor.peek());
+}
+
+#[test]
+fn consume_until_any() {
+    let mut cursor = Cursor::new("key=value;rest".as_bytes());
+    assert_eq!(cursor.consume_until_any(b"=;"), Some(b'='));
+    assert_eq!(cursor.index(), 3);
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_until_any(b",;:!?="), Some(b';'));
+    assert_eq!(cursor.index(), 9);
+    
+    cursor.advance();
+    assert_eq!(cursor.consume_until_any(b"=;"), None);
+    assert!(!cursor.has_next());
+    assert_eq!(cursor.consume_until_any(b",;:!?="), None);
+}
+
+#[test]
+fn with_position() {
+    let mut cursor = Cursor::new(b"a\x80");
+    let r = cursor.next_char();
+    assert_eq!(cursor.with_position(r), Ok(Some('a')));
+    
+    let r = cursor.next_char();
+    assert_eq!(cursor.with_position(r), Err(PositionedError {
+        error: Error::EncounteredContinuationByte,
+        index: 1,
+        found: Some(0x80),
+        line_column: (1, 2),
+        name: None,
+    }));
+}
+
+#[test]
+fn is_at_char_boundary() {
+    let mut cursor = Cursor::new("a€".as_bytes());
+    let expected = [true, true, false, false, true];
+    
+    for boundary in expected {
+        assert_eq!(cursor.is_at_char_boundary(), boundary);
+        cursor.advance();
+    }
+}
+
+#[test]
+fn recorded_by() {
+    let mut cursor = Cursor::new(b"x = 1;\xFF");
+    let (name, text) = cursor.recorded_by(|cursor| {
+        let name = cursor.consume_c_identifier();
+        cursor.consume_until_any(b";");
+        name
+    });
+    assert_eq!(name, Some("x"));
+    assert_eq!(text, "x = 1");
+    assert_eq!(cursor.peek(), Some(b';'));
+}
+
+#[test]
+#[should_panic(expected = "recorded bytes are not valid UTF-8")]
+fn recorded_by_invalid_utf8() {
+    let mut cursor = Cursor::new(b"\xFF");
+    cursor.recorded_by(|cursor| cursor.advance());
+}
+
+#[test]
+fn consume_u64_separated() {
+    let mut cursor = Cursor::new(b"1_000_000 ");
+    assert_eq!(cursor.consume_u64_separated(b'_', 10), Some(1_000_000));
+    assert_eq!(cursor.peek(), Some(b' '));
+    
+    let mut cursor = Cursor::new(b"ff_FFg");
+    assert_eq!(cursor.consume_u64_separated(b'_', 16), Some(0xFFFF));
+    assert_eq!(cursor.peek(), Some(b'g'));
+    
+    for input in [&b"_1"[..], b"1_", b"1__0", b"1_x", b"", b"x"] {
+        let mut cursor = Cursor::new(input);
+        assert_eq!(cursor.consume_u64_separated(b'_', 10), None);
+        assert_eq!(cursor.byte_offset(), 0);
+    }
+    
+    let mut cursor = Cursor::new(b"18_446_744_073_709_551_616");
+    assert_eq!(cursor.consume_u64_separated(b'_', 10), None);
+    assert_eq!(cursor.byte_offset(), 0);
+    
+    let mut cursor = Cursor::new(b"18'446'744'073'709'551'615");
+    assert_eq!(cursor.consume_u64_separated(b'\'', 10), Some(u64::MAX));
+}
+
+#[test]
+fn available_char_len() {
+    assert_eq!(Cursor::new(b"a").available_char_len(), Some(1));
+    assert_eq!(Cursor::new("€x".as_bytes()).available_char_len(), Some(3));
+    assert_eq!(Cursor::new(b"\xE2\x82").available_char_len(), Some(2));
+    assert_eq!(Cursor::new(b"\xF0").available_char_len(), Some(1));
+    assert_eq!(Cursor::new(b"\x80").available_char_len(), None);
+    assert_eq!(Cursor::new(b"").available_char_len(), None);
+}
+
+#[test]
+fn restore_line_column() {
+    let mut cursor = Cursor::new(b"a\nb\r\nc\rd");
+    cursor.advance();
+    let checkpoint = cursor.checkpoint();
+    
+    while cursor.has_next() {
+        cursor.advance();
+    }
+    assert_eq!(cursor.line_column(), (4, 2));
+    
+    cursor.restore(checkpoint);
+    assert_eq!(cursor.line_column(), (1, 2));
+    assert_eq!(cursor.index(), 1);
+}
+
+#[test]
+fn consume_ascii_digits() {
+    let mut cursor = Cursor::new(b"12345678901234567890123x");
+    assert_eq!(cursor.consume_ascii_digits(), "12345678901234567890123");
+    assert_eq!(cursor.consume_ascii_digits(), "");
+    assert_eq!(cursor.peek(), Some(b'x'));
+}
+
+#[test]
+fn expect_char() {
+    let mut cursor = Cursor::new("»a\u{FF}".as_bytes());
+    assert_eq!(cursor.expect_char('»'), Ok(()));
+    assert_eq!(cursor.expect_char('»'), Err(ExpectCharError::Mismatch('a', 2)));
+    assert_eq!(cursor.expect_char('a'), Ok(()));
+    assert_eq!(cursor.expect_char('\u{FF}'), Ok(()));
+    assert_eq!(cursor.expect_char('a'), Err(ExpectCharError::End(5)));
+    
+    let mut cursor = Cursor::new(b"\xC3");
+    assert_eq!(cursor.expect_char('a'), Err(ExpectCharError::Invalid(Error::Missing2ndOf2, 0)));
+    assert_eq!(cursor.byte_offset(), 0);
+}
+
+#[test]
+fn byte_len_of_next_chars() {
+    let cursor = Cursor::new("a€ü\u{1F600}".as_bytes());
+    assert_eq!(cursor.byte_len_of_next_chars(0), Ok(0));
+    assert_eq!(cursor.byte_len_of_next_chars(2), Ok(4));
+    assert_eq!(cursor.byte_len_of_next_chars(4), Ok(10));
+    assert_eq!(Cursor::new(b"a\x80").byte_len_of_next_chars(2), Err(Error::EncounteredContinuationByte));
+}
+
+#[test]
+fn byte_len_of_next_chars_incomplete() {
+    let cursor = Cursor::new("a€".as_bytes());
+    assert_eq!(cursor.byte_len_of_next_chars(3), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"a\xE2\x82").byte_len_of_next_chars(2), Err(Error::Incomplete));
+    assert_eq!(Cursor::new(b"").byte_len_of_next_chars(1), Err(Error::Incomplete));
+}
+
+#[test]
+fn fold_bytes() {
+    let mut cursor = Cursor::new(b"header:\x01\x02\x04");
+    cursor.consume_until_any(b":");
+    cursor.advance();
+    assert_eq!(cursor.fold_bytes(0, |acc, x| acc ^ x), 7);
+    assert!(!cursor.has_next());
+    assert_eq!(cursor.fold_bytes(1u32, |acc, x| acc + x as u32), 1);
+}
+
+#[test]
+fn skip_continuation_bytes() {
+    let mut cursor = Cursor::new(b"\x82\xACa\xE2\x82\xAC");
+    assert_eq!(cursor.skip_continuation_bytes(), 2);
+    assert_eq!(cursor.skip_continuation_bytes(), 0);
+    assert_eq!(cursor.peek(), Some(b'a'));
+    
+    cursor.advance();
+    assert_eq!(cursor.skip_continuation_bytes(), 0);
+    cursor.advance();
+    assert_eq!(cursor.skip_continuation_bytes(), 2);
+    assert!(cursor.is_at_char_boundary());
+}
+
+#[test]
+fn column_bytes() {
+    let mut cursor = Cursor::new("ab\nü\rx".as_bytes());
+    assert_eq!(cursor.column_bytes(), 0);
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.column_bytes(), 2);
+    cursor.advance();
+    assert_eq!(cursor.column_bytes(), 0);
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.column_bytes(), 2);
+    cursor.advance();
+    cursor.advance();
+    assert_eq!(cursor.column_bytes(), 1);
+}
+
+#[test]
+fn peek_char_lossy() {
+    assert_eq!(Cursor::new("€".as_bytes()).peek_char_lossy(), Some('€'));
+    assert_eq!(Cursor::new(b"\xE2\x82").peek_char_lossy(), Some('\u{FFFD}'));
+    assert_eq!(Cursor::new(b"\xC0\xAF").peek_char_lossy(), Some('\u{FFFD}'));
+    assert_eq!(Cursor::new(b"").peek_char_lossy(), None);
+}
+
+#[test]
+fn consume_class_str() {
+    let mut class = [false; 256];
+    class[b'a' as usize] = true;
+    class[b'b' as usize] = true;
+    class[0xC3] = true;
+    
+    let mut cursor = Cursor::new("abba\u{E4}".as_bytes());
+    assert_eq!(cursor.consume_class_str(&class), "abba");
+    assert_eq!(cursor.consume_class_str(&class), "");
+    assert_eq!(cursor.peek(), Some(0xC3));
+}
+
+#[test]
+fn fork() {
+    let mut cursor = Cursor::new(b"abc");
+    cursor.advance();
+    let mut fork = cursor.fork();
+    fork.advance();
+    assert_eq!(cursor.peek(), Some(b'b'));
+    assert_eq!(fork.peek(), Some(b'c'));
+    
+    cursor.restore(fork.checkpoint());
+    assert_eq!(cursor.peek(), Some(b'c'));
+    assert_eq!(cursor.index(), 2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the checkpoint belongs to another cursor")]
+fn restore_not_forked() {
+    let long = Cursor::new("abc".as_bytes());
+    let mut tail = Cursor::new(&long.as_slice()[1..]);
+    tail.advance();
+    let mut long = long;
+    long.restore(tail.checkpoint());
+}
+
+#[test]
+fn chunks() {
+    let mut cursor = Cursor::new(b"abcdefg");
+    cursor.advance();
+    let mut chunks = cursor.chunks(4);
+    assert_eq!(chunks.size_hint(), (2, Some(2)));
+    assert_eq!(chunks.next(), Some(&b"bcde"[..]));
+    assert_eq!(chunks.next(), Some(&b"fg"[..]));
+    assert_eq!(chunks.next(), None);
+    assert!(!cursor.has_next());
+}
+
+#[test]
+fn peek_line() {
+    let mut cursor = Cursor::new(b"# skip\r\nkeep\n\n\xFF");
+    assert_eq!(cursor.peek_line(), Ok(Some("# skip")));
+    assert_eq!(cursor.byte_offset(), 0);
+    
+    cursor.consume_line_bytes();
+    assert_eq!(cursor.peek_line(), Ok(Some("keep")));
+    cursor.consume_line_bytes();
+    assert_eq!(cursor.peek_line(), Ok(Some("")));
+    cursor.consume_line_bytes();
+    assert!(cursor.peek_line().is_err());
+    cursor.consume_line_bytes();
+    assert_eq!(cursor.peek_line(), Ok(None));
+}
+
+#[test]
+fn validate_and_advance_all() {
+    let text = "The quick brown fox — jumps over the lazy dog. ü";
+    let mut cursor = Cursor::new(text.as_bytes());
+    assert_eq!(cursor.validate_and_advance_all(), Ok(text.chars().count()));
+    assert!(!cursor.has_next());
+    
+    let mut cursor = Cursor::new(b"0123456789abc\xE2\x28\xA1");
+    cursor.reset_index(100);
+    assert_eq!(cursor.validate_and_advance_all(), Err((113, Error::Invalid2ndOf3)));
+    assert_eq!(cursor.byte_offset(), 13);
+    assert_eq!(cursor.peek(), Some(0xE2));
+    
+    assert_eq!(Cursor::new(b"").validate_and_advance_all(), Ok(0));
+}
+
+#[test]
+fn new_with_base() {
+    let mut cursor = Cursor::new_with_base(b"ab", 1000);
+    assert_eq!(cursor.index(), 1000);
+    cursor.advance();
+    assert_eq!(cursor.index(), 1001);
+    assert_eq!(cursor.byte_offset(), 1);
+    assert_eq!(cursor.offset_of(1001), Some(1));
+    assert_eq!(cursor.offset_of(999), None);
+}
+
+#[test]
+fn consume_indent() {
+    let indents = |input: &[u8], tab_width| {
+        let mut cursor = Cursor::new(input);
+        (cursor.consume_indent(tab_width), cursor.peek())
+    };
+    
+    assert_eq!(indents(b"    x", 4), (4, Some(b'x')));
+    assert_eq!(indents(b"  \tx", 4), (4, Some(b'x')));
+    assert_eq!(indents(b"\t  x", 4), (6, Some(b'x')));
+    assert_eq!(indents(b"\t\t", 8), (16, None));
+    assert_eq!(indents(b" \t", 0), (2, None));
+    assert_eq!(indents(b"x ", 4), (0, Some(b'x')));
+}
+
+#[test]
+fn try_into_str() {
+    let mut cursor = Cursor::new("a€".as_bytes());
+    cursor.advance();
+    assert_eq!(cursor.try_into_str(), Ok("a€"));
+    assert!(Cursor::new(b"\xFF").try_into_str().is_err());
+}
+
+#[test]
+fn recover_to_next_line() {
+    let mut cursor = Cursor::new(b"bad line\r\nok\rnext");
+    cursor.advance();
+    assert_eq!(cursor.recover_to_next_line(), 9);
+    assert_eq!(cursor.peek(), Some(b'o'));
+    assert_eq!(cursor.recover_to_next_line(), 3);
+    assert_eq!(cursor.recover_to_next_line(), 4);
+    assert_eq!(cursor.recover_to_next_line(), 0);
+}
+
+#[test]
+fn consume_template() {
+    let mut cursor = Cursor::new(br"{a {b} \} \{ c}d");
+    assert_eq!(cursor.consume_template(b'{', b'}', b'\\'), Ok(&br"a {b} \} \{ c"[..]));
+    assert_eq!(cursor.peek(), Some(b'd'));
+    assert_eq!(cursor.consume_template(b'{', b'}', b'\\'), Err(TemplateError::MissingOpen));
+    
+    for input in [&br"x{a {b}"[..], br"x{a\}", br"x{a\"] {
+        let mut cursor = Cursor::new(input);
+        cursor.advance();
+        assert_eq!(cursor.consume_template(b'{', b'}', b'\\'), Err(TemplateError::Unterminated(1)));
+        assert_eq!(cursor.byte_offset(), 1);
+    }
+}
+
+#[test]
+fn byte_histogram() {
+    let mut cursor = Cursor::new(b"xaabaa\0");
+    cursor.advance();
+    let histogram = cursor.byte_histogram();
+    assert_eq!(histogram[b'a' as usize], 4);
+    assert_eq!(histogram[b'b' as usize], 1);
+    assert_eq!(histogram[0], 1);
+    assert_eq!(histogram[b'x' as usize], 0);
+    assert_eq!(histogram.iter().sum::<u32>(), 6);
+    assert_eq!(cursor.byte_offset(), 1);
+}
+
+#[test]
+fn consume_while_max() {
+    let mut cursor = Cursor::new(b"abc1");
+    assert_eq!(cursor.consume_while_max(3, |x| x.is_ascii_alphabetic()), Ok(3));
+    assert_eq!(cursor.peek(), Some(b'1'));
+    
+    let mut cursor = Cursor::new(b"abcd");
+    assert_eq!(cursor.consume_while_max(3, |x| x.is_ascii_alphabetic()), Err(3));
+    assert_eq!(cursor.peek(), Some(b'd'));
+    
+    let mut cursor = Cursor::new(b"abc");
+    assert_eq!(cursor.consume_while_max(3, |x| x.is_ascii_alphabetic()), Ok(3));
+    assert_eq!(cursor.consume_while_max(0, |_| true), Ok(0));
+}
+
+#[test]
+fn peek_back_char() {
+    let mut cursor = Cursor::new(b"a\xE2\x82\xAC\x80");
+    assert_eq!(cursor.peek_back_char(), None);
+    cursor.advance();
+    assert_eq!(cursor.peek_back_char(), Some('a'));
+    cursor.advance_char().unwrap();
+    assert_eq!(cursor.peek_back_char(), Some('€'));
+    assert_eq!(cursor.byte_offset(), 4);
+    cursor.advance();
+    assert_eq!(cursor.peek_back_char(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn consume_prefix_tree() {
+    let tree = bytes::PrefixTree::build(&[(b"in", 1), (b"int", 2), (b"if", 3), (b"i", 4), (b"if", 5)]);
+    
+    let mut cursor = Cursor::new(b"integer");
+    assert_eq!(cursor.consume_prefix_tree(&tree), Some(2));
+    assert_eq!(cursor.peek(), Some(b'e'));
+    
+    let mut cursor = Cursor::new(b"inx");
+    assert_eq!(cursor.consume_prefix_tree(&tree), Some(1));
+    
+    let mut cursor = Cursor::new(b"if");
+    assert_eq!(cursor.consume_prefix_tree(&tree), Some(5));
+    
+    let mut cursor = Cursor::new(b"ix");
+    assert_eq!(cursor.consume_prefix_tree(&tree), Some(4));
+    
+    let mut cursor = Cursor::new(b"x");
+    assert_eq!(cursor.consume_prefix_tree(&tree), None);
+    assert_eq!(cursor.byte_offset(), 0);
+}
+
+#[test]
+fn consume_crlf() {
+    let mut cursor = Cursor::new(b"\r\nx");
+    assert_eq!(cursor.consume_crlf(), Ok(()));
+    assert_eq!(cursor.consume_crlf(), Err(CrlfError::Missing(Some(b'x'))));
+    assert_eq!(cursor.peek(), Some(b'x'));
+    
+    assert_eq!(Cursor::new(b"\rx").consume_crlf(), Err(CrlfError::LoneCr(Some(b'x'))));
+    assert_eq!(Cursor::new(b"\r").consume_crlf(), Err(CrlfError::LoneCr(None)));
+    assert_eq!(Cursor::new(b"\n").consume_crlf(), Err(CrlfError::LoneLf));
+    assert_eq!(Cursor::new(b"").consume_crlf(), Err(CrlfError::Missing(None)));
+}
+
+#[test]
+fn offset_within() {
+    let parent = b"outer [inner] rest";
+    let mut cursor = Cursor::new(&parent[7..12]);
+    cursor.advance();
+    assert_eq!(cursor.offset_within(parent), Some(8));
+    assert_eq!(cursor.offset_within(&parent[7..]), Some(1));
+    assert_eq!(cursor.offset_within(&parent[..7]), None);
+    assert_eq!(cursor.offset_within(&parent[8..]), None);
+    assert_eq!(cursor.offset_within(&[b'i'; 5]), None);
+}
+
+#[test]
+fn expect_one_of() {
+    let mut cursor = Cursor::new(b"-x");
+    assert_eq!(cursor.expect_one_of(b"+-)"), Ok(b'-'));
+    assert_eq!(cursor.expect_one_of(b"+-)"), Err(ExpectError { found: Some(b'x'), expected: b"+-)" }));
+    assert_eq!(cursor.peek(), Some(b'x'));
+    cursor.advance();
+    assert_eq!(cursor.expect_one_of(b"x"), Err(ExpectError { found: None, expected: b"x" }));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn expect_error_display() {
+    use alloc::string::ToString;
+    
+    let error = ExpectError { found: Some(b'x'), expected: b"+-)" };
+    assert_eq!(error.to_string(), "expected one of `+`, `-`, `)`, found `x`");
+    
+    let error = ExpectError { found: None, expected: b"\n" };
+    assert_eq!(error.to_string(), "expected one of `\\n`, found the end of the input");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn resolve_positions() {
+    use alloc::vec::Vec;
+    
+    let input = "ab\r\nc\tü\rd\n".as_bytes();
+    let mut cursor = Cursor::new(input);
+    cursor.set_tab_width(4);
+    let offsets: Vec<u64> = (0..=input.len() as u64 + 1).collect();
+    
+    let expected: Vec<(u32, u32)> = offsets
+        .iter()
+        .map(|&offset| {
+            let mut at = Cursor::new(input);
+            at.set_tab_width(4);
+            for _ in 0..offset.min(input.len() as u64) {
+                at.advance();
+            }
+            at.line_column()
+        })
+        .collect();
+    
+    assert_eq!(cursor.resolve_positions(&offsets), expected);
+    assert_eq!(cursor.resolve_positions(&[5, 0, 11]), [(2, 2), (1, 1), (4, 1)]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn resolve_positions_index() {
+    let mut cursor = Cursor::new_with_base(b"ab\ncd", 1000);
+    cursor.advance();
+    cursor.advance();
+    cursor.advance();
+    cursor.advance();
+    
+    assert_eq!(cursor.resolve_positions(&[cursor.index()]), [cursor.line_column()]);
+    assert_eq!(cursor.resolve_positions(&[1004]), [(2, 2)]);
+    assert_eq!(cursor.resolve_positions(&[999, 2000]), [(1, 1), (2, 3)]);
+    
+    cursor.reset_index(10);
+    assert_eq!(cursor.resolve_positions(&[5, 6, 10]), [(1, 1), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn consume_while_stateful() {
+    let mut cursor = Cursor::new(b"(a(b))c)");
+    let mut depth = 0u32;
+    let len = cursor.consume_while_stateful(&mut depth, |depth, x| {
+        match x {
+            b'(' => *depth += 1,
+            b')' if *depth == 0 => return false,
+            b')' => *depth -= 1,
+            _ => {}
+        }
+        true
+    });
+    assert_eq!(len, 7);
+    assert_eq!(depth, 0);
+    assert_eq!(cursor.peek(), Some(b')'));
+}
+
+#[test]
+fn check_invariants() {
+    let mut cursor = Cursor::new(b"a\r\nb");
+    cursor.check_invariants();
+    let _ = cursor.next_lfn();
+    let _ = cursor.next_lfn();
+    cursor.check_invariants();
+    cursor.reset_index(10);
+    let prev = cursor.set_end(1).unwrap();
+    cursor.advance();
+    cursor.check_invariants();
+    cursor.restore_end(prev);
+    cursor.check_invariants();
+}
+
+#[test]
+fn consume_escape() {
+    let escape = |input: &[u8]| {
+        let mut cursor = Cursor::new(input);
+        let result = cursor.consume_escape();
+        (result, cursor.byte_offset())
+    };
+    
+    for (x, c) in [(b'n', '\n'), (b't', '\t'), (b'r', '\r'), (b'\\', '\\'), (b'"', '"'), (b'\'', '\''), (b'0', '\0')] {
+        assert_eq!(escape(&[x]), (Ok(c), 1));
+    }
+    
+    assert_eq!(escape(b"x41z"), (Ok('A'), 3));
+    assert_eq!(escape(b"x7f"), (Ok('\x7F'), 3));
+    assert_eq!(escape(b"u{1F600}z"), (Ok('\u{1F600}'), 8));
+    assert_eq!(escape(b"u{e9}"), (Ok('é'), 5));
+    
+    assert_eq!(escape(b"q"), (Err(EscapeError::Unknown(b'q')), 0));
+    assert_eq!(escape(b""), (Err(EscapeError::End), 0));
+    assert_eq!(escape(b"x4"), (Err(EscapeError::End), 0));
+    assert_eq!(escape(b"x4g"), (Err(EscapeError::InvalidHex), 0));
+    assert_eq!(escape(b"x80"), (Err(EscapeError::OutOfRange(0x80)), 0));
+    assert_eq!(escape(b"u41"), (Err(EscapeError::InvalidHex), 0));
+    assert_eq!(escape(b"u{}"), (Err(EscapeError::InvalidHex), 0));
+    assert_eq!(escape(b"u{1234567}"), (Err(EscapeError::InvalidHex), 0));
+    assert_eq!(escape(b"u{41"), (Err(EscapeError::End), 0));
+    assert_eq!(escape(b"u{D800}"), (Err(EscapeError::OutOfRange(0xD800)), 0));
+    assert_eq!(escape(b"u{110000}"), (Err(EscapeError::OutOfRange(0x110000)), 0));
+}
+
+#[test]
+fn new_named() {
+    let cursor = Cursor::new_named(b"a", "main.txt");
+    assert_eq!(cursor.input_name(), Some("main.txt"));
+    assert_eq!(cursor.into_remaining().input_name(), Some("main.txt"));
+    assert_eq!(Cursor::new(b"a").input_name(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn positioned_error_display() {
+    use alloc::string::ToString;
+    
+    let mut cursor = Cursor::new_named(b"ok\n  \x80", "main.txt");
+    cursor.consume_until_any(b"\x80");
+    let error = cursor.with_position(cursor.peek_char()).unwrap_err();
+    assert_eq!(error.name, Some("main.txt"));
+    assert_eq!(error.to_string(), "main.txt:2:3: EncounteredContinuationByte");
+    
+    let cursor = Cursor::new(b"\x80");
+    let error = cursor.with_position(cursor.peek_char()).unwrap_err();
+    assert_eq!(error.to_string(), "1:1: EncounteredContinuationByte");
+}
+
+#[test]
+fn skip_inline_whitespace() {
+    let mut cursor = Cursor::new(b" \t\x0B\x0C\r\n x");
+    assert_eq!(cursor.skip_inline_whitespace(), 4);
+    assert_eq!(cursor.peek(), Some(b'\r'));
+    assert_eq!(cursor.skip_inline_whitespace(), 0);
+    cursor.consume_newline();
+    assert_eq!(cursor.skip_inline_whitespace(), 1);
+    assert_eq!(cursor.peek(), Some(b'x'));
+}
+
+#[test]
+fn recorder_current_slice() {
+    let mut cursor = Cursor::new(b"abc");
+    let mut recorder = cursor.begin_recording();
+    recorder.advance();
+    let partial = recorder.current_slice();
+    recorder.advance();
+    assert_eq!(partial, b"a");
+    assert_eq!(recorder.current_slice(), b"ab");
+    assert_eq!(recorder.stop(), b"ab");
+}
+
+#[test]
+#[should_panic(expected = "the cursor is before the recording")]
+fn recorder_stop_before_start() {
+    let mut cursor = Cursor::new(b"abc");
+    cursor.advance();
+    let mut recorder = cursor.begin_recording();
+    recorder.rewind_lfn();
+    recorder.stop();
+}
+
+#[test]
+fn consume_delimited() {
+    let mut cursor = Cursor::new(b"<!-- a <!-- b -->c -->");
+    assert_eq!(cursor.consume_delimited(b"<!--", b"-->"), Ok(&b" a <!-- b "[..]));
+    assert_eq!(cursor.peek(), Some(b'c'));
+    assert_eq!(cursor.consume_delimited(b"<!--", b"-->"), Err(DelimError::MissingOpen));
+    
+    let mut cursor = Cursor::new(b"/**/");
+    assert_eq!(cursor.consume_delimited(b"/*", b"*/"), Ok(&b""[..]));
+    assert!(!cursor.has_next());
+    
+    let mut cursor = Cursor::new(b"x/* a *");
+    cursor.advance();
+    assert_eq!(cursor.consume_delimited(b"/*", b"*/"), Err(DelimError::Unterminated(1)));
+    assert_eq!(cursor.byte_offset(), 1);
+}
+
+#[test]
+fn remaining_starts_with_ignore_ascii_case() {
+    let cursor = Cursor::new("Content-Length: 5 Ä".as_bytes());
+    assert!(cursor.remaining_starts_with_ignore_ascii_case(b"content-length:"));
+    assert!(cursor.remaining_starts_with_ignore_ascii_case(b"CONTENT"));
+    assert!(cursor.remaining_starts_with_ignore_ascii_case(b""));
+    assert!(!cursor.remaining_starts_with_ignore_ascii_case(b"content_length"));
+    assert!(!cursor.remaining_starts_with_ignore_ascii_case("Content-Length: 5 Ä and more".as_bytes()));
+    
+    let cursor = Cursor::new("Ä".as_bytes());
+    assert!(cursor.remaining_starts_with_ignore_ascii_case("Ä".as_bytes()));
+    assert!(!cursor.remaining_starts_with_ignore_ascii_case("ä".as_bytes()));
+    assert_eq!(cursor.byte_offset(), 0);
+}
+
+#[test]
+fn consume_while_char_str() {
+    let mut cursor = Cursor::new("größe_2 = 1".as_bytes());
+    assert_eq!(cursor.consume_while_char_str(|c| c.is_alphanumeric() || c == '_'), Ok("größe_2"));
+    assert_eq!(cursor.peek(), Some(b' '));
+    assert_eq!(cursor.consume_while_char_str(char::is_alphanumeric), Ok(""));
+    
+    let mut cursor = Cursor::new(b"ab\xFFc");
+    assert_eq!(cursor.consume_while_char_str(char::is_alphanumeric), Err(Error::EncounteredContinuationByte));
+    assert_eq!(cursor.byte_offset(), 2);
+    
+    let mut cursor = Cursor::new(b"abc");
+    assert_eq!(cursor.consume_while_char_str(|_| true), Ok("abc"));
+}
+
+#[test]
+fn rewind_n_chars() {
+    let mut cursor = Cursor::new("a€🦀b".as_bytes());
+    while cursor.has_next() {
+        cursor.advance();
+    }
+    
+    assert_eq!(cursor.rewind_n_chars(2), 2);
+    assert_eq!(cursor.index(), 4);
+    assert_eq!(cursor.peek_char(), Ok(Some('🦀')));
+    assert_eq!(cursor.rewind_n_chars(5), 2);
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.rewind_n_chars(1), 0);
+    
+    let mut cursor = Cursor::new(b"\x80\x80\x80\x80\x80a");
+    while cursor.has_next() {
+        cursor.advance();
+    }
+    assert_eq!(cursor.rewind_n_chars(2), 2);
+    assert_eq!(cursor.byte_offset(), 1);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+#[should_panic(expected = "keywords must not be empty")]
+fn prefix_tree_empty_keyword() {
+    bytes::PrefixTree::build(&[(b"if", 1), (b"", 2)]);
+}
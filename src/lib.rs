@@ -1,5 +1,6 @@
 #![no_std]
-#![feature(const_ptr_sub_ptr)]
-#![feature(ptr_sub_ptr)]
 
-pub mod bytes;
\ No newline at end of file
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod bytes;